@@ -0,0 +1,471 @@
+//! A `hyprctl` like command line tool built on top of hyprrust.
+//!
+//! Every command and every data type of the crate is exposed as a subcommand, their arguments are
+//! parsed using the argument types of the crate:
+//! ```sh
+//! hyprrust command focus-window class:kitty
+//! hyprrust command resize-active-window "exact 50% 50%"
+//! hyprrust --json get clients
+//! hyprrust --instance 1 get decorations address:0x55d4e3a3c7a0
+//! ```
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use clap::{Arg, ArgAction, ArgMatches};
+use hyprrust::commands::prelude::*;
+use hyprrust::data::*;
+use hyprrust::errors::ArgumentParseError;
+use hyprrust::HyprlandConnection;
+use serde::Serialize;
+
+type CliCommand = clap::Command;
+
+fn parse<T>(s: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.parse().map_err(|e: T::Err| e.to_string())
+}
+
+/// Turns the name of a command function or of a request into the name of its subcommand.
+fn subcommand_name(function: &str) -> String {
+    function.replace(['_', ' '], "-")
+}
+
+macro_rules! commands {
+    (
+        $($name:ident($($field:ident: $type:ty),*)),*;
+        fallible: $($f_name:ident($($f_field:ident: $f_type:ty),*)),* $(,)?
+    ) => {
+        fn command_subcommands() -> Vec<CliCommand> {
+            vec![$(
+                CliCommand::new(subcommand_name(stringify!($name)))
+                    $(.arg(
+                        Arg::new(stringify!($field))
+                            .required(true)
+                            .help(stringify!($type))
+                            .value_parser(parse::<$type>),
+                    ))*
+            ),*, $(
+                CliCommand::new(subcommand_name(stringify!($f_name)))
+                    $(.arg(
+                        Arg::new(stringify!($f_field))
+                            .required(true)
+                            .help(stringify!($f_type))
+                            .value_parser(parse::<$f_type>),
+                    ))*
+            ),*]
+        }
+
+        /// Returns the command named `name`, or `None` if there is no such command. Commands
+        /// that check their arguments as a whole return an error when they are invalid.
+        fn get_command(
+            name: &str,
+            matches: &ArgMatches,
+        ) -> Option<Result<Command, ArgumentParseError>> {
+            $(
+                if name == subcommand_name(stringify!($name)) {
+                    return Some(Ok($name($(
+                        matches.get_one::<$type>(stringify!($field)).unwrap().clone()
+                    ),*)));
+                }
+            )*
+            $(
+                if name == subcommand_name(stringify!($f_name)) {
+                    return Some($f_name($(
+                        matches.get_one::<$f_type>(stringify!($f_field)).unwrap().clone()
+                    ),*));
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+commands! {
+    execr(program: String),
+    pass(to: WindowArgument),
+    send_shortcut(modifier: ModArgument, key: KeyArgument, window: WindowArgument),
+    kill_active(),
+    force_kill_active(),
+    close_window(which: WindowArgument),
+    kill_window(which: WindowArgument),
+    signal_active(signal: SignalArgument),
+    signal_window(which: WindowArgument, signal: SignalArgument),
+    go_to_work_space(to: WorkspaceArgument),
+    move_to_workspace(to: WorkspaceArgument, which: WindowArgument),
+    move_to_workspace_silent(to: WorkspaceArgument, which: WindowArgument),
+    toggle_floating(which: WindowArgument),
+    set_floating(which: WindowArgument),
+    set_tiled(which: WindowArgument),
+    fullscreen(),
+    fullscreen_maximize(),
+    set_fullscreen_state(internal: FullscreenState, client: FullscreenState),
+    dpms(state: BoolChangeArgument),
+    dpms_monitor(state: BoolChangeArgument, monitor: MonitorArgument),
+    pin_window(which: WindowArgument),
+    move_focus(to: DirectionArgument),
+    move_window_in_direction(direction: DirectionArgument, silent: bool),
+    move_window_to_monitor(monitor: MonitorArgument, silent: bool),
+    swap_window(to: DirectionArgument),
+    center_window(with_exclude: bool),
+    resize_active_window(to: ResizeArgument),
+    move_active_window(to: ResizeArgument),
+    resize_window(to: ResizeArgument, which: WindowArgument),
+    move_window_by(by: ResizeArgument, which: WindowArgument),
+    cycle_next(options: CycleNextArguments),
+    swap_with_next(),
+    swap_with_prev(),
+    tag_window(tag: TagArgument, which: WindowArgument),
+    focus_window(which: WindowArgument),
+    focus_monitor(which: MonitorArgument),
+    set_split_ratio(split: FloatArgument),
+    move_cursor_to_corner(which: CornerArgument),
+    move_cursor(x: i32, y: i32),
+    rename_workspace(which_id: String, new_name: String),
+    exit_hyprland(),
+    force_render_reload(),
+    move_current_workspace_to_monitor(to: MonitorArgument),
+    focus_workspace_on_current_monitor(which: WorkspaceArgument),
+    move_workspace_to_monitor(which: WorkspaceArgument, to: MonitorArgument),
+    swap_active_workspaces(first: MonitorArgument, second: MonitorArgument),
+    alt_zorder(zheight: ZHeightArgument, which: WindowArgument),
+    toggle_special_workspace(which: String),
+    focus_urgent_or_last(),
+    toggle_group(),
+    change_group_active(to: ChangeGroupActiveArgument),
+    focus_current_or_last(),
+    lock_groups(lock_state: LockGroupArgument),
+    lock_active_group(lock_state: LockGroupArgument),
+    move_into_group(direction: DirectionArgument),
+    move_out_of_group(which: WindowArgument),
+    move_window_or_group(direction: DirectionArgument),
+    deny_window_from_group(set: BoolChangeArgument),
+    set_ignore_group_lock(set: BoolChangeArgument),
+    execute_global_shortcut(which: String),
+    send_custom_event(data: String),
+    set_property(window: WindowArgument, name: String, value: String),
+    toggle_swallow(),
+    set_property_locked(window: WindowArgument, name: String, value: String),
+    toggle_pseudo(which: WindowArgument),
+    toggle_split(),
+    swap_split(),
+    move_group_window_forward(),
+    move_group_window_back(),
+    toggle_workspace_option(option: WorkspaceOptArgument),
+    focus_window_by_class(class: String),
+    submap(name: String),
+    reset_submap(),
+    send_key_state(modifier: ModArgument, key: KeyArgument, state: KeyStateArgument, window: WindowArgument),
+    bring_active_to_top(),
+    force_idle(seconds: f32),
+    layout_message(message: String),
+    dwindle_layout_message(message: DwindleLayoutMessage),
+    set_config_value(variable: String, value: String),
+    reload_config(),
+    kill_window_mode(),
+    set_cursor_theme(theme_name: String, size: i32),
+    set_error(color: ColorArgument, message: String),
+    disable_error(),
+    notify(icon: NotifyIconArgument, time_ms: i32, color: ColorArgument, message: String),
+    dismiss_notifications(amount: i32),
+    dismiss_all_notifications(),
+    create_output(backend: OutputBackendArgument),
+    create_named_output(backend: OutputBackendArgument, name: String),
+    remove_output(name: String),
+    load_plugin(path: String),
+    unload_plugin(path: String),
+    switch_xkb_layout(keyboard: KeyboardArgument, layout: XkbLayoutArgument),
+    mouse(pressed: bool, action: MouseActionArgument);
+    fallible:
+    exec(rules: ExecRules, program: String),
+    master_layout_message(message: MasterLayoutMessage),
+}
+
+/// Prints `data` as pretty JSON or using its `Debug` implementation.
+fn print_data<T>(out: &mut impl Write, data: &T, json: bool) -> Result<(), Box<dyn Error>>
+where
+    T: Serialize + Debug,
+{
+    if json {
+        writeln!(out, "{}", serde_json::to_string_pretty(data)?)?;
+    } else {
+        writeln!(out, "{:#?}", data)?;
+    }
+
+    Ok(())
+}
+
+macro_rules! queries {
+    (
+        plain: $($plain:ty),*;
+        argument: $($arg_name:literal => $arg:ty),*;
+        optional_argument: $($opt_name:literal => $opt:ty),*;
+    ) => {
+        fn query_subcommands() -> Vec<CliCommand> {
+            let mut subcommands = vec![$(
+                CliCommand::new(subcommand_name(<$plain as HyprlandData>::get_command())),
+            )*];
+            $(subcommands.push(CliCommand::new($arg_name).arg(
+                Arg::new("argument")
+                    .required(true)
+                    .value_parser(parse::<<$arg as HyprlandDataWithArgument>::Argument>),
+            ));)*
+            $(subcommands.push(CliCommand::new($opt_name).arg(
+                Arg::new("argument")
+                    .value_parser(parse::<<$opt as HyprlandDataWithArgument>::Argument>),
+            ));)*
+
+            subcommands
+        }
+
+        fn run_query(
+            conn: &HyprlandConnection,
+            name: &str,
+            matches: &ArgMatches,
+            out: &mut impl Write,
+            json: bool,
+        ) -> Result<(), Box<dyn Error>> {
+            $(
+                if name == subcommand_name(<$plain as HyprlandData>::get_command()) {
+                    return print_data(out, &conn.get_sync::<$plain>()?, json);
+                }
+            )*
+            $(
+                if name == $arg_name {
+                    let arg = matches
+                        .get_one::<<$arg as HyprlandDataWithArgument>::Argument>("argument")
+                        .unwrap()
+                        .clone();
+                    return print_data(out, &conn.get_with_argument_sync::<$arg>(arg)?, json);
+                }
+            )*
+            $(
+                if name == $opt_name {
+                    let data = match matches
+                        .get_one::<<$opt as HyprlandDataWithArgument>::Argument>("argument")
+                    {
+                        Some(arg) => conn.get_with_argument_sync::<$opt>(arg.clone())?,
+                        None => conn.get_sync::<$opt>()?,
+                    };
+                    return print_data(out, &data, json);
+                }
+            )*
+
+            Err(format!("unknown data type {}", name).into())
+        }
+    };
+}
+
+queries! {
+    plain: Version, Workspace, Workspaces, WorkspaceRules, Window, Windows, Devices, Binds,
+        Layers, CursorPosition, Animations, ConfigErrors, Layouts, Splash, GlobalShortcuts,
+        Instances, RollingLog, SystemInfo, Locked, Submap, Descriptions, LayerRules, Plugins;
+    argument: "decorations" => Decorations, "getoption" => GetOption;
+    optional_argument: "monitors" => Monitors;
+}
+
+fn cli() -> CliCommand {
+    CliCommand::new("hyprrust")
+        .about("Control Hyprland and query its state")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("instance")
+                .long("instance")
+                .short('i')
+                .global(true)
+                .help(
+                    "The signature or the index of the Hyprland instance to use, \
+                     instances are numbered from the oldest as listed by `instances`",
+                ),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .short('j')
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print data as JSON"),
+        )
+        .subcommand(
+            CliCommand::new("command")
+                .about("Send a command to Hyprland")
+                .subcommand_required(true)
+                .subcommands(command_subcommands()),
+        )
+        .subcommand(
+            CliCommand::new("get")
+                .about("Request data from Hyprland")
+                .subcommand_required(true)
+                .subcommands(query_subcommands()),
+        )
+        .subcommand(CliCommand::new("instances").about("List the running Hyprland instances"))
+}
+
+/// Returns a connection to `instance`, which is either an index into
+/// [`HyprlandConnection::get_instances_by_age`], where the oldest instance comes first, or an instance
+/// signature.
+fn connect(instance: Option<&String>) -> Result<HyprlandConnection, Box<dyn Error>> {
+    let Some(instance) = instance else {
+        return Ok(HyprlandConnection::current()
+            .map_err(|_| "HYPRLAND_INSTANCE_SIGNATURE is not set, is Hyprland running?")?);
+    };
+
+    let instances = HyprlandConnection::get_instances_by_age()?;
+    if let Some(signature) = instance
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| instances.get(index))
+    {
+        Ok(HyprlandConnection::new(signature.clone()))
+    } else if instances.contains(instance) {
+        Ok(HyprlandConnection::new(instance.clone()))
+    } else {
+        Err(format!("no Hyprland instance matches {}", instance).into())
+    }
+}
+
+fn run(
+    conn: &HyprlandConnection,
+    matches: &ArgMatches,
+    out: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let json = matches.get_flag("json");
+
+    match matches.subcommand() {
+        Some(("command", matches)) => {
+            let (name, matches) = matches.subcommand().unwrap();
+            let command = get_command(name, matches).ok_or("unknown command")??;
+            conn.send_command_sync(&command)?;
+            writeln!(out, "ok")?;
+        }
+        Some(("get", matches)) => {
+            let (name, matches) = matches.subcommand().unwrap();
+            run_query(conn, name, matches, out, json)?;
+        }
+        _ => unreachable!("subcommands are required"),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+
+    let result = if matches.subcommand_name() == Some("instances") {
+        HyprlandConnection::get_instances_by_age()
+            .map(|instances| {
+                for (i, instance) in instances.iter().enumerate() {
+                    println!("{}: {}", i, instance);
+                }
+            })
+            .map_err(|e| e.into())
+    } else {
+        connect(matches.get_one::<String>("instance"))
+            .and_then(|conn| run(&conn, &matches, &mut io::stdout()))
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("hyprrust: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod hyprrust_tests {
+    use super::*;
+    use hyprrust::testing::MockHyprland;
+
+    fn run_args(mock: &MockHyprland, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        let matches = cli().try_get_matches_from(["hyprrust"].iter().chain(args))?;
+        let mut out = Vec::new();
+        run(&mock.connection(), &matches, &mut out)?;
+
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        cli().debug_assert();
+    }
+
+    #[test]
+    fn test_cli_commands() {
+        let mock = MockHyprland::start().unwrap();
+
+        assert_eq!(
+            run_args(&mock, &["command", "focus-window", "class:kitty"]).unwrap(),
+            "ok\n"
+        );
+        run_args(&mock, &["command", "focus-window", "kitty"]).unwrap();
+        run_args(&mock, &["command", "resize-active-window", "exact 50% 40"]).unwrap();
+        run_args(&mock, &["command", "kill-active"]).unwrap();
+        assert_eq!(
+            mock.received(),
+            [
+                "dispatch focuswindow class:kitty",
+                "dispatch focuswindow class:kitty",
+                "dispatch resizeactive exact 50% 40",
+                "dispatch killactive"
+            ]
+        );
+
+        assert!(run_args(&mock, &["command", "focus-window", "pid:kitty"]).is_err());
+        assert_eq!(mock.received().len(), 4);
+
+        mock.clear_received();
+        run_args(&mock, &["command", "exec", "[float]", "kitty"]).unwrap();
+        run_args(
+            &mock,
+            &["command", "master-layout-message", "swapwithmaster"],
+        )
+        .unwrap();
+        run_args(&mock, &["command", "mouse", "true", "movewindow"]).unwrap();
+        assert_eq!(
+            mock.received(),
+            [
+                "dispatch exec [float] kitty",
+                "dispatch layoutmsg swapwithmaster",
+                "dispatch mouse 1movewindow",
+            ]
+        );
+
+        assert!(run_args(&mock, &["command", "exec", "[]", " "]).is_err());
+        assert_eq!(mock.received().len(), 3);
+    }
+
+    #[test]
+    fn test_cli_queries() {
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("cursorpos", r#"{"x": 10, "y": 20}"#);
+        mock.reply(
+            "getoption general:gaps_in",
+            r#"{"option": "general:gaps_in", "int": 5, "set": true}"#,
+        );
+
+        let json = run_args(&mock, &["--json", "get", "cursorpos"]).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({"x": 10, "y": 20})
+        );
+        assert!(run_args(&mock, &["get", "cursorpos"])
+            .unwrap()
+            .starts_with("CursorPosition {"));
+        assert!(run_args(&mock, &["get", "getoption", "general:gaps_in"]).is_ok());
+        mock.reply("plugin list", "no plugins loaded");
+        assert_eq!(
+            run_args(&mock, &["--json", "get", "plugin-list"]).unwrap(),
+            "[]\n"
+        );
+        assert!(run_args(&mock, &["get", "decorations", "nope"]).is_err());
+    }
+}
@@ -0,0 +1,1605 @@
+//! Typed access to config options, checked against the schema Hyprland reports.
+//!
+//! [`ConfigSchema`] is built from [`Descriptions`] and knows the type, range and allowed values
+//! of every option. [`HyprlandConnection::get_option`] reads an option as any type implementing
+//! [`ConfigValue`] that matches its declared type, and [`HyprlandConnection::set_option`] rejects
+//! a value before sending it. Besides the primitive types, options can be read as [`Vec2`],
+//! [`Gradient`], [`CssGap`] and [`ColorArgument`].
+//! [`OptionSet`] describes many options at once, ie. loaded from a JSON or TOML (needs the
+//! `toml` feature) file, and only sets the ones that changed. [`ConfigOverride`] sets options
+//! until it's dropped.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use hyprrust::config::{ConfigSchema, Gradient};
+//! use hyprrust::HyprlandConnection;
+//!
+//! let conn = HyprlandConnection::current()?;
+//! let schema = ConfigSchema::new(&conn).await?;
+//!
+//! let border_size = conn
+//!     .get_option::<i64>(&schema, "general:border_size")
+//!     .await?;
+//! let border: Gradient = conn
+//!     .get_option(&schema, "general:col.active_border")
+//!     .await?;
+//! println!("{} {:?}", border_size, border);
+//!
+//! conn.set_option(&schema, "general:border_size", &(border_size + 1))
+//!     .await?;
+//! // Out of range, nothing is sent to Hyprland
+//! assert!(conn.set_option(&schema, "decoration:active_opacity", &2.0).await.is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Descriptions`]: crate::data::Descriptions
+
+use std::collections::{btree_map, BTreeMap, HashMap};
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::arguments::{Argument, ColorArgument};
+use crate::commands::{set_config_value, Command};
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::ctl::data::{get_batch_from_requests, split_batch_reply};
+use crate::data::{
+    Descriptions, GetOption, HyprlandDataWithArgument, OptionDescription, OptionType, OptionValue,
+};
+use crate::errors::{ArgumentParseError, ConfigError};
+use crate::HyprlandConnection;
+
+/// A value made of two floats, ie. `general:resize_corner` sizes or `decoration:shadow:offset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub fn new(x: f32, y: f32) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.x, self.y)
+    }
+}
+
+impl FromStr for Vec2 {
+    type Err = ArgumentParseError;
+
+    /// Parses two floats separated by spaces or a comma.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "vector");
+        let mut floats = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<f32>().map_err(|_| err()));
+
+        match (floats.next(), floats.next(), floats.next()) {
+            (Some(x), Some(y), None) => Ok(Vec2::new(x?, y?)),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// Gaps given like CSS margins, ie. `general:gaps_in`. One to four numbers are accepted, in the
+/// same order as CSS: top, right, bottom and left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CssGap {
+    pub top: i64,
+    pub right: i64,
+    pub bottom: i64,
+    pub left: i64,
+}
+
+impl CssGap {
+    pub fn new(top: i64, right: i64, bottom: i64, left: i64) -> Self {
+        CssGap {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Returns the same gap on every side.
+    pub fn uniform(gap: i64) -> Self {
+        CssGap::new(gap, gap, gap, gap)
+    }
+
+    /// Returns the gap when it's the same on every side.
+    pub fn as_uniform(&self) -> Option<i64> {
+        (self.top == self.right && self.top == self.bottom && self.top == self.left)
+            .then_some(self.top)
+    }
+}
+
+impl fmt::Display for CssGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.top, self.right, self.bottom, self.left
+        )
+    }
+}
+
+impl FromStr for CssGap {
+    type Err = ArgumentParseError;
+
+    /// Parses one to four integers separated by spaces or commas, as Hyprland does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let gaps = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<i64>())
+            .collect::<Result<Vec<i64>, _>>()
+            .map_err(|_| ArgumentParseError::new(s, "gap"))?;
+
+        match gaps[..] {
+            [all] => Ok(CssGap::uniform(all)),
+            [vertical, horizontal] => Ok(CssGap::new(vertical, horizontal, vertical, horizontal)),
+            [top, horizontal, bottom] => Ok(CssGap::new(top, horizontal, bottom, horizontal)),
+            [top, right, bottom, left] => Ok(CssGap::new(top, right, bottom, left)),
+            _ => Err(ArgumentParseError::new(s, "gap")),
+        }
+    }
+}
+
+/// A border gradient, ie. `general:col.active_border`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub colors: Vec<ColorArgument>,
+    /// The angle in degrees, Hyprland uses 0 when there is none
+    pub angle: Option<f32>,
+}
+
+impl Gradient {
+    pub fn new(colors: Vec<ColorArgument>, angle: Option<f32>) -> Self {
+        Gradient { colors, angle }
+    }
+}
+
+impl fmt::Display for Gradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colors: Vec<String> = self
+            .colors
+            .iter()
+            .map(Argument::to_argument_string)
+            .collect();
+        write!(f, "{}", colors.join(" "))?;
+        if let Some(angle) = self.angle {
+            write!(f, " {}deg", angle)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Gradient {
+    type Err = ArgumentParseError;
+
+    /// Parses gradients as written in the config, and as Hyprland reports them, where colors are
+    /// written as `aarrggbb` without a prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "gradient");
+        let mut colors = Vec::new();
+        let mut angle = None;
+
+        for part in s.split_whitespace() {
+            if angle.is_some() {
+                return Err(err());
+            }
+            if let Some(degrees) = part.strip_suffix("deg") {
+                angle = Some(degrees.parse().map_err(|_| err())?);
+            } else if part.len() == 8 && part.chars().all(|c| c.is_ascii_hexdigit()) {
+                colors.push(format!("0x{}", part).parse()?);
+            } else {
+                colors.push(part.parse()?);
+            }
+        }
+
+        if colors.is_empty() {
+            return Err(err());
+        }
+        Ok(Gradient::new(colors, angle))
+    }
+}
+
+/// A type an option can be read as and set to.
+pub trait ConfigValue: Sized {
+    /// Returns whether options declared as `option_type` hold this type of value.
+    fn has_type(option_type: OptionType) -> bool;
+
+    /// Converts the value reported by `getoption`, `None` if it holds another type.
+    fn from_option_value(value: &OptionValue) -> Option<Self>;
+
+    /// Returns the value formatted for the config.
+    fn to_config_string(&self) -> String;
+}
+
+impl ConfigValue for bool {
+    fn has_type(option_type: OptionType) -> bool {
+        option_type == OptionType::Bool
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Int(0) => Some(false),
+            OptionValue::Int(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for i64 {
+    /// Choices are reported and can be set by their index. Gaps are read as [`CssGap`] and
+    /// colors as [`ColorArgument`].
+    fn has_type(option_type: OptionType) -> bool {
+        matches!(option_type, OptionType::Int | OptionType::Choice)
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Int(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for i32 {
+    fn has_type(option_type: OptionType) -> bool {
+        i64::has_type(option_type)
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        i64::from_option_value(value).and_then(|int| int.try_into().ok())
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for f32 {
+    fn has_type(option_type: OptionType) -> bool {
+        option_type == OptionType::Float
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for String {
+    /// Every option can be set using a string, it's still validated by [`ConfigSchema`].
+    fn has_type(_option_type: OptionType) -> bool {
+        true
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::String(s) | OptionValue::Custom(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ConfigValue for Vec2 {
+    fn has_type(option_type: OptionType) -> bool {
+        option_type == OptionType::Vector
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Vec2([x, y]) => Some(Vec2::new(*x, *y)),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for CssGap {
+    fn has_type(option_type: OptionType) -> bool {
+        matches!(option_type, OptionType::StringShort | OptionType::Other(_))
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Int(int) => Some(CssGap::uniform(*int)),
+            OptionValue::String(s) | OptionValue::Custom(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for ColorArgument {
+    fn has_type(option_type: OptionType) -> bool {
+        option_type == OptionType::Color
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Int(int) => Some(color_from_int(*int)),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_argument_string()
+    }
+}
+
+impl ConfigValue for Gradient {
+    fn has_type(option_type: OptionType) -> bool {
+        option_type == OptionType::Gradient
+    }
+
+    fn from_option_value(value: &OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Custom(s) | OptionValue::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Returns the number in `data` under `key`, for vectors `axis` picks the component.
+fn limit(data: &serde_json::Value, key: &str, axis: Option<usize>) -> Option<f64> {
+    let value = data.get(key)?;
+    match axis {
+        None => value.as_f64(),
+        Some(axis) => value
+            .get(axis)
+            .or_else(|| value.get(["x", "y"][axis]))
+            .and_then(serde_json::Value::as_f64),
+    }
+}
+
+fn in_range(data: &serde_json::Value, value: f64, axis: Option<usize>) -> bool {
+    let below = matches!(limit(data, "min", axis), Some(min) if value < min);
+    let above = matches!(limit(data, "max", axis), Some(max) if value > max);
+    !below && !above
+}
+
+/// Returns the index of the first choice and the name of every choice of a choice option.
+fn choices(data: &serde_json::Value) -> (i64, Vec<&str>) {
+    let choices = data
+        .get("options")
+        .and_then(serde_json::Value::as_str)
+        .map(|options| options.split(',').map(str::trim).collect())
+        .unwrap_or_default();
+    let first = data
+        .get("firstIndex")
+        .and_then(serde_json::Value::as_i64)
+        .unwrap_or(0);
+
+    (first, choices)
+}
+
+/// Returns the index Hyprland reports for the choice named `name`.
+fn choice_index(option: &OptionDescription, name: &str) -> Option<i64> {
+    if option.option_type != OptionType::Choice {
+        return None;
+    }
+
+    let (first, choices) = choices(&option.data);
+    let position = choices.iter().position(|choice| *choice == name)?;
+    Some(first + position as i64)
+}
+
+/// The type, range and allowed values of every option, as reported by `descriptions`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSchema {
+    options: HashMap<String, OptionDescription>,
+}
+
+impl ConfigSchema {
+    /// Requests the schema from Hyprland.
+    #[cfg(feature = "async")]
+    pub async fn new(conn: &HyprlandConnection) -> Result<Self, io::Error> {
+        Ok(conn.get::<Descriptions>().await?.into())
+    }
+
+    /// The blocking counterpart of [`Self::new`].
+    #[cfg(feature = "sync")]
+    pub fn new_sync(conn: &HyprlandConnection) -> Result<Self, io::Error> {
+        Ok(conn.get_sync::<Descriptions>()?.into())
+    }
+
+    /// Returns the description of the option named `name`.
+    pub fn option(&self, name: &str) -> Option<&OptionDescription> {
+        self.options.get(name)
+    }
+
+    /// Checks that the option named `name` holds values of type `T`.
+    pub fn check_type<T: ConfigValue>(
+        &self,
+        name: &str,
+    ) -> Result<&OptionDescription, ConfigError> {
+        let option = self
+            .option(name)
+            .ok_or_else(|| ConfigError::UnknownOption(name.to_string()))?;
+        if !T::has_type(option.option_type) {
+            return Err(ConfigError::TypeMismatch {
+                option: name.to_string(),
+            });
+        }
+
+        Ok(option)
+    }
+
+    /// Checks that `value` can be set for the option named `name`: it has to have the type of
+    /// the option and be in its range, choices can be given by name or by index.
+    pub fn validate(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        let option = self
+            .option(name)
+            .ok_or_else(|| ConfigError::UnknownOption(name.to_string()))?;
+        let data = &option.data;
+        let value = value.trim();
+
+        let valid = match option.option_type {
+            OptionType::Bool => {
+                ["true", "false", "yes", "no", "on", "off", "1", "0"].contains(&value)
+            }
+            OptionType::Int => value
+                .parse::<i64>()
+                .is_ok_and(|int| in_range(data, int as f64, None)),
+            OptionType::Float => value
+                .parse::<f64>()
+                .is_ok_and(|float| in_range(data, float, None)),
+            OptionType::Color => {
+                value.parse::<i64>().is_ok() || value.parse::<ColorArgument>().is_ok()
+            }
+            OptionType::Choice => {
+                let (first, choices) = choices(data);
+                let last = first + choices.len() as i64;
+
+                choices.contains(&value)
+                    || value
+                        .parse::<i64>()
+                        .is_ok_and(|index| (first..last).contains(&index))
+            }
+            OptionType::Gradient => value.parse::<Gradient>().is_ok(),
+            OptionType::Vector => value.parse::<Vec2>().is_ok_and(|vec| {
+                in_range(data, vec.x as f64, Some(0)) && in_range(data, vec.y as f64, Some(1))
+            }),
+            OptionType::StringShort | OptionType::StringLong | OptionType::Other(_) => {
+                !value.contains('\n')
+            }
+        };
+
+        if !valid {
+            return Err(ConfigError::InvalidValue {
+                option: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<Descriptions> for ConfigSchema {
+    fn from(value: Descriptions) -> Self {
+        ConfigSchema {
+            options: value
+                .iter()
+                .map(|option| (option.name.clone(), option.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Converts the reply to `getoption name`.
+fn option_from_reply<T: ConfigValue>(name: &str, reply: &str) -> Result<T, ConfigError> {
+    T::from_option_value(&option_value_from_reply(name, reply)?).ok_or_else(|| {
+        ConfigError::TypeMismatch {
+            option: name.to_string(),
+        }
+    })
+}
+
+fn option_value_from_reply(name: &str, reply: &str) -> Result<OptionValue, ConfigError> {
+    if reply.trim() == "no such option" {
+        return Err(ConfigError::UnknownOption(name.to_string()));
+    }
+
+    let option = GetOption::from_reply(reply).map_err(io::Error::from)?;
+    Ok(option.value().clone())
+}
+
+impl HyprlandConnection {
+    /// Returns the current value of the option named `name`. Fails with
+    /// [`ConfigError::TypeMismatch`] if `schema` declares the option with another type than `T`,
+    /// or if Hyprland reports a value of another type.
+    #[cfg(feature = "async")]
+    pub async fn get_option<T: ConfigValue>(
+        &self,
+        schema: &ConfigSchema,
+        name: &str,
+    ) -> Result<T, ConfigError> {
+        schema.check_type::<T>(name)?;
+        let reply = self
+            .send_raw_message(&format!("-j/getoption {}", name))
+            .await?;
+        option_from_reply(name, &reply)
+    }
+
+    /// The blocking counterpart of [`Self::get_option`].
+    #[cfg(feature = "sync")]
+    pub fn get_option_sync<T: ConfigValue>(
+        &self,
+        schema: &ConfigSchema,
+        name: &str,
+    ) -> Result<T, ConfigError> {
+        schema.check_type::<T>(name)?;
+        let reply = self.send_raw_message_sync(&format!("-j/getoption {}", name))?;
+        option_from_reply(name, &reply)
+    }
+
+    /// Sets the option named `name` to `value`, after checking it against `schema`. See
+    /// [`ConfigSchema::validate`].
+    #[cfg(feature = "async")]
+    pub async fn set_option<T: ConfigValue>(
+        &self,
+        schema: &ConfigSchema,
+        name: &str,
+        value: &T,
+    ) -> Result<(), ConfigError> {
+        schema.check_type::<T>(name)?;
+        let value = value.to_config_string();
+        schema.validate(name, &value)?;
+
+        Ok(self
+            .send_command(&set_config_value(name.to_string(), value))
+            .await?)
+    }
+
+    /// The blocking counterpart of [`Self::set_option`].
+    #[cfg(feature = "sync")]
+    pub fn set_option_sync<T: ConfigValue>(
+        &self,
+        schema: &ConfigSchema,
+        name: &str,
+        value: &T,
+    ) -> Result<(), ConfigError> {
+        schema.check_type::<T>(name)?;
+        let value = value.to_config_string();
+        schema.validate(name, &value)?;
+
+        Ok(self.send_command_sync(&set_config_value(name.to_string(), value))?)
+    }
+}
+
+/// Returns `value` formatted so it can be set again using `keyword`.
+fn option_value_to_config_string(value: &OptionValue) -> String {
+    match value {
+        OptionValue::Int(int) => int.to_string(),
+        OptionValue::Float(float) => float.to_string(),
+        OptionValue::String(s) => s.clone(),
+        // Gradients are reported with colors Hyprland can't parse back, without the `0x` prefix
+        OptionValue::Custom(s) => s
+            .parse::<Gradient>()
+            .map(|gradient| gradient.to_string())
+            .unwrap_or_else(|_| s.clone()),
+        OptionValue::Vec2([x, y]) => Vec2::new(*x, *y).to_string(),
+    }
+}
+
+/// Builds the batch that requests every option in `names`.
+#[cfg(any(feature = "async", feature = "sync"))]
+fn get_options_batch(names: &[String]) -> String {
+    get_batch_from_requests(names.iter().map(|name| format!("getoption {}", name)))
+}
+
+/// Converts the reply to [`get_options_batch`] to the value of every option.
+#[cfg(any(feature = "async", feature = "sync"))]
+fn options_from_batch_reply(
+    names: &[String],
+    reply: &str,
+) -> Result<Vec<OptionValue>, ConfigError> {
+    names
+        .iter()
+        .zip(split_batch_reply(reply, names.len())?)
+        .map(|(name, reply)| option_value_from_reply(name, reply))
+        .collect()
+}
+
+/// Pairs every name in `names` with its value, in config format.
+fn originals(names: &[String], values: Vec<OptionValue>) -> Vec<(String, String)> {
+    names
+        .iter()
+        .cloned()
+        .zip(values.iter().map(option_value_to_config_string))
+        .collect()
+}
+
+fn keyword_commands(values: &[(String, String)]) -> Vec<Command> {
+    values
+        .iter()
+        .map(|(name, value)| set_config_value(name.clone(), value.clone()))
+        .collect()
+}
+
+/// Splits `values` into the names of the options and the `keyword` commands that set them.
+fn override_commands<I, K, V>(values: I) -> (Vec<String>, Vec<Command>)
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    let values: Vec<(String, String)> = values
+        .into_iter()
+        .map(|(name, value)| (name.into(), value.into()))
+        .collect();
+    let names = values.iter().map(|(name, _)| name.clone()).collect();
+
+    (names, keyword_commands(&values))
+}
+
+/// Config options that were set to new values, and are set back to the values they had before
+/// when the guard is dropped or [reverted](Self::revert).
+///
+/// Overrides can be nested, every guard remembers the values it replaced, so guards dropped in
+/// the reverse order they were created in (ie. at the end of nested scopes) leave the config
+/// as it was.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use hyprrust::config::ConfigOverride;
+/// use hyprrust::HyprlandConnection;
+///
+/// let conn = HyprlandConnection::current()?;
+/// let gaming = ConfigOverride::new(
+///     &conn,
+///     [("animations:enabled", "false"), ("decoration:blur:enabled", "false")],
+/// )
+/// .await?;
+/// {
+///     let _no_gaps = ConfigOverride::new(&conn, [("general:gaps_out", "0")]).await?;
+///     // ...
+/// }
+/// gaming.revert().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(any(feature = "async", feature = "sync"))]
+#[derive(Debug)]
+pub struct ConfigOverride {
+    conn: HyprlandConnection,
+    originals: Vec<(String, String)>,
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+impl ConfigOverride {
+    /// Reads the current value of every option in `values` in a single request, then sets them
+    /// to the new values in a second one. If Hyprland rejects any of the new values, the options
+    /// are set back before returning the error.
+    #[cfg(feature = "async")]
+    pub async fn new<I, K, V>(conn: &HyprlandConnection, values: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let (names, commands) = override_commands(values);
+        let mut guard = ConfigOverride {
+            conn: conn.with_instance(conn.instance().to_string()),
+            originals: Vec::new(),
+        };
+        if names.is_empty() {
+            return Ok(guard);
+        }
+
+        let reply = conn.send_raw_message(&get_options_batch(&names)).await?;
+        guard.originals = originals(&names, options_from_batch_reply(&names, &reply)?);
+
+        if let Err(mut errors) = conn.send_recipe(&commands).await {
+            guard.revert().await?;
+            return Err(errors.remove(0).into());
+        }
+        Ok(guard)
+    }
+
+    /// The blocking counterpart of [`Self::new`].
+    #[cfg(feature = "sync")]
+    pub fn new_sync<I, K, V>(conn: &HyprlandConnection, values: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let (names, commands) = override_commands(values);
+        let mut guard = ConfigOverride {
+            conn: conn.with_instance(conn.instance().to_string()),
+            originals: Vec::new(),
+        };
+        if names.is_empty() {
+            return Ok(guard);
+        }
+
+        let reply = conn.send_raw_message_sync(&get_options_batch(&names))?;
+        guard.originals = originals(&names, options_from_batch_reply(&names, &reply)?);
+
+        if let Err(mut errors) = conn.send_recipe_sync(&commands) {
+            guard.revert_sync()?;
+            return Err(errors.remove(0).into());
+        }
+        Ok(guard)
+    }
+
+    /// Returns the options that were overridden, with the values they had before.
+    pub fn originals(&self) -> &[(String, String)] {
+        &self.originals
+    }
+
+    /// Sets the options back to their original values, reporting the first error.
+    #[cfg(feature = "async")]
+    pub async fn revert(mut self) -> Result<(), ConfigError> {
+        let commands = keyword_commands(&std::mem::take(&mut self.originals));
+        if commands.is_empty() {
+            return Ok(());
+        }
+
+        self.conn
+            .send_recipe(&commands)
+            .await
+            .map_err(|mut errors| errors.remove(0).into())
+    }
+
+    /// The blocking counterpart of [`Self::revert`].
+    #[cfg(feature = "sync")]
+    pub fn revert_sync(mut self) -> Result<(), ConfigError> {
+        let commands = keyword_commands(&std::mem::take(&mut self.originals));
+        if commands.is_empty() {
+            return Ok(());
+        }
+
+        self.conn
+            .send_recipe_sync(&commands)
+            .map_err(|mut errors| errors.remove(0).into())
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+impl Drop for ConfigOverride {
+    /// Restores the options with a blocking request, so they are restored before the guard is
+    /// gone, even while panicking or when the runtime is shutting down.
+    fn drop(&mut self) {
+        let commands = keyword_commands(&std::mem::take(&mut self.originals));
+        if commands.is_empty() {
+            return;
+        }
+
+        let _ = self.conn.send_recipe_blocking(&commands);
+    }
+}
+
+/// Returns the color Hyprland reports as an integer, in `0xaarrggbb` order.
+fn color_from_int(int: i64) -> ColorArgument {
+    let [a, r, g, b] = (int as u32).to_be_bytes();
+    ColorArgument::new(r, g, b, a)
+}
+
+/// Returns whether Hyprland reporting `current` means the option is already set to `desired`.
+/// Choices given by name can only be compared using the description of the option, without it
+/// they never match.
+fn option_value_matches(
+    current: &OptionValue,
+    desired: &str,
+    option: Option<&OptionDescription>,
+) -> bool {
+    let desired = desired.trim();
+    match current {
+        OptionValue::Int(int) => match desired {
+            "true" | "yes" | "on" => *int == 1,
+            "false" | "no" | "off" => *int == 0,
+            _ => {
+                desired.parse::<i64>().ok() == Some(*int)
+                    || desired
+                        .parse::<ColorArgument>()
+                        .is_ok_and(|color| color == color_from_int(*int))
+                    || option.and_then(|option| choice_index(option, desired)) == Some(*int)
+            }
+        },
+        OptionValue::Float(float) => desired.parse::<f32>().ok() == Some(*float),
+        OptionValue::String(s) => s.trim() == desired,
+        OptionValue::Custom(s) => match (s.parse::<Gradient>(), desired.parse::<Gradient>()) {
+            (Ok(current), Ok(desired)) => {
+                current.colors == desired.colors
+                    && current.angle.unwrap_or(0.0) == desired.angle.unwrap_or(0.0)
+            }
+            _ => s.split_whitespace().eq(desired.split_whitespace()),
+        },
+        OptionValue::Vec2([x, y]) => desired.parse::<Vec2>().ok() == Some(Vec2::new(*x, *y)),
+    }
+}
+
+/// An option whose value is different from the one in an [`OptionSet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionChange {
+    pub name: String,
+    /// The current value, formatted for the config
+    pub old: String,
+    pub new: String,
+}
+
+/// A set of options and the values they should have, which can be compared with the running
+/// compositor and applied without reloading the config.
+///
+/// Sets can be built in code or deserialized from any format supported by serde. Nested tables
+/// are joined using `:`, so these two are the same set:
+/// ```toml
+/// [general]
+/// border_size = 2
+/// "col.active_border" = "rgb(ff0000)"
+///
+/// [decoration.blur]
+/// enabled = false
+/// ```
+/// ```json
+/// {
+///     "general:border_size": 2,
+///     "general:col.active_border": "rgb(ff0000)",
+///     "decoration:blur:enabled": false
+/// }
+/// ```
+///
+/// A set can also be used as a temporary override, see [`ConfigOverride::new`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptionSet {
+    options: BTreeMap<String, String>,
+}
+
+impl OptionSet {
+    pub fn new() -> Self {
+        OptionSet::default()
+    }
+
+    pub fn with_option<T: ConfigValue>(mut self, name: &str, value: &T) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Sets the value of the option named `name`, returning the previous one.
+    pub fn insert<T: ConfigValue>(&mut self, name: &str, value: &T) -> Option<String> {
+        self.options
+            .insert(name.to_string(), value.to_config_string())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.options
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.options.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.options.keys().cloned().collect()
+    }
+
+    fn changes(
+        &self,
+        current: Vec<OptionValue>,
+        schema: Option<&ConfigSchema>,
+    ) -> Vec<OptionChange> {
+        self.options
+            .iter()
+            .zip(current)
+            .filter(|((name, new), old)| {
+                !option_value_matches(old, new, schema.and_then(|schema| schema.option(name)))
+            })
+            .map(|((name, new), old)| OptionChange {
+                name: name.clone(),
+                old: option_value_to_config_string(&old),
+                new: new.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the options whose current value is different from the one in this set, all the
+    /// values are read in a single request. Choices given by name are only compared when
+    /// `schema` is given, otherwise they are always reported as changed.
+    #[cfg(feature = "async")]
+    pub async fn diff(
+        &self,
+        conn: &HyprlandConnection,
+        schema: Option<&ConfigSchema>,
+    ) -> Result<Vec<OptionChange>, ConfigError> {
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let names = self.names();
+        let reply = conn.send_raw_message(&get_options_batch(&names)).await?;
+        Ok(self.changes(options_from_batch_reply(&names, &reply)?, schema))
+    }
+
+    /// The blocking counterpart of [`Self::diff`].
+    #[cfg(feature = "sync")]
+    pub fn diff_sync(
+        &self,
+        conn: &HyprlandConnection,
+        schema: Option<&ConfigSchema>,
+    ) -> Result<Vec<OptionChange>, ConfigError> {
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let names = self.names();
+        let reply = conn.send_raw_message_sync(&get_options_batch(&names))?;
+        Ok(self.changes(options_from_batch_reply(&names, &reply)?, schema))
+    }
+
+    /// Sets the options that have a different value than the one in this set, in a single
+    /// request, and returns what changed. See [`Self::diff`].
+    #[cfg(feature = "async")]
+    pub async fn apply(
+        &self,
+        conn: &HyprlandConnection,
+        schema: Option<&ConfigSchema>,
+    ) -> Result<Vec<OptionChange>, ConfigError> {
+        let changes = self.diff(conn, schema).await?;
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+
+        conn.send_recipe(&change_commands(&changes))
+            .await
+            .map_err(|mut errors| errors.remove(0))?;
+        Ok(changes)
+    }
+
+    /// The blocking counterpart of [`Self::apply`].
+    #[cfg(feature = "sync")]
+    pub fn apply_sync(
+        &self,
+        conn: &HyprlandConnection,
+        schema: Option<&ConfigSchema>,
+    ) -> Result<Vec<OptionChange>, ConfigError> {
+        let changes = self.diff_sync(conn, schema)?;
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+
+        conn.send_recipe_sync(&change_commands(&changes))
+            .map_err(|mut errors| errors.remove(0))?;
+        Ok(changes)
+    }
+}
+
+fn change_commands(changes: &[OptionChange]) -> Vec<Command> {
+    changes
+        .iter()
+        .map(|change| set_config_value(change.name.clone(), change.new.clone()))
+        .collect()
+}
+
+impl<'a> IntoIterator for &'a OptionSet {
+    type Item = (&'a String, &'a String);
+    type IntoIter = btree_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.options.iter()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for OptionSet {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        OptionSet {
+            options: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawOptionValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Section(BTreeMap<String, RawOptionValue>),
+}
+
+fn flatten_options(
+    options: &mut BTreeMap<String, String>,
+    prefix: &str,
+    values: BTreeMap<String, RawOptionValue>,
+) {
+    for (key, value) in values {
+        let name = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}:{}", prefix, key)
+        };
+
+        let value = match value {
+            RawOptionValue::Bool(bool) => bool.to_string(),
+            RawOptionValue::Int(int) => int.to_string(),
+            RawOptionValue::Float(float) => float.to_string(),
+            RawOptionValue::String(s) => s,
+            RawOptionValue::Section(section) => {
+                flatten_options(options, &name, section);
+                continue;
+            }
+        };
+        options.insert(name, value);
+    }
+}
+
+impl<'de> Deserialize<'de> for OptionSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut options = BTreeMap::new();
+        flatten_options(&mut options, "", BTreeMap::deserialize(deserializer)?);
+        Ok(OptionSet { options })
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::{ConfigSchema, CssGap, Gradient, OptionSet, Vec2};
+    use crate::arguments::ColorArgument;
+    use crate::data::{Descriptions, HyprlandData};
+    use crate::errors::ConfigError;
+
+    const DESCRIPTIONS: &str = r#"[
+        {"value": "general:border_size", "description": "", "type": 1, "flags": 0,
+         "data": {"default": 1, "min": 0, "max": 20, "current": 2, "explicit": true}},
+        {"value": "decoration:active_opacity", "description": "", "type": 2, "flags": 0,
+         "data": {"default": 1.0, "min": 0.0, "max": 1.0, "current": 1.0, "explicit": false}},
+        {"value": "general:layout", "description": "", "type": 3, "flags": 0,
+         "data": {"default": "dwindle", "current": "dwindle", "explicit": false}},
+        {"value": "general:gaps_in", "description": "", "type": 3, "flags": 0,
+         "data": {"default": "5", "current": "5", "explicit": false}},
+        {"value": "general:gaps_out", "description": "", "type": 3, "flags": 0,
+         "data": {"default": "20", "current": "20", "explicit": false}},
+        {"value": "misc:background_color", "description": "", "type": 5, "flags": 0,
+         "data": {"default": 4278190080, "current": 4278190080, "explicit": false}},
+        {"value": "misc:vfr", "description": "", "type": 0, "flags": 0,
+         "data": {"default": true, "current": true, "explicit": false}},
+        {"value": "master:new_status", "description": "", "type": 6, "flags": 0,
+         "data": {"firstIndex": 0, "options": "master,slave,inherit", "current": 1}},
+        {"value": "general:col.active_border", "description": "", "type": 7, "flags": 0,
+         "data": {"default": "0xffffffff", "current": "0xffffffff", "explicit": false}},
+        {"value": "decoration:shadow:offset", "description": "", "type": 8, "flags": 0,
+         "data": {"default": {"x": 0, "y": 0}, "min": {"x": -250, "y": -250},
+                  "max": {"x": 250, "y": 250}, "current": {"x": 0, "y": 0}}}
+    ]"#;
+
+    fn schema() -> ConfigSchema {
+        Descriptions::from_reply(DESCRIPTIONS).unwrap().into()
+    }
+
+    #[test]
+    fn test_validate_values() {
+        let schema = schema();
+
+        for (option, value) in [
+            ("general:border_size", "20"),
+            ("decoration:active_opacity", "0.5"),
+            ("general:layout", "master"),
+            ("misc:vfr", "off"),
+            ("master:new_status", "inherit"),
+            ("master:new_status", "2"),
+            (
+                "general:col.active_border",
+                "rgba(33ccffee) 0xff00ff99 45deg",
+            ),
+            ("decoration:shadow:offset", "10 -20"),
+        ] {
+            assert!(
+                schema.validate(option, value).is_ok(),
+                "{} {}",
+                option,
+                value
+            );
+        }
+
+        for (option, value) in [
+            ("general:border_size", "21"),
+            ("general:border_size", "1.5"),
+            ("decoration:active_opacity", "2"),
+            ("misc:vfr", "maybe"),
+            ("master:new_status", "3"),
+            ("general:col.active_border", "45deg"),
+            ("decoration:shadow:offset", "10 300"),
+        ] {
+            assert!(
+                matches!(
+                    schema.validate(option, value),
+                    Err(ConfigError::InvalidValue { .. })
+                ),
+                "{} {}",
+                option,
+                value
+            );
+        }
+
+        assert!(matches!(
+            schema.validate("general:foo", "1"),
+            Err(ConfigError::UnknownOption(_))
+        ));
+        assert!(schema.check_type::<i32>("general:border_size").is_ok());
+        assert!(schema.check_type::<i64>("master:new_status").is_ok());
+        assert!(schema.check_type::<CssGap>("general:gaps_out").is_ok());
+        assert!(schema
+            .check_type::<ColorArgument>("misc:background_color")
+            .is_ok());
+        for option in [
+            "general:gaps_out",
+            "misc:background_color",
+            "general:layout",
+        ] {
+            assert!(
+                matches!(
+                    schema.check_type::<i64>(option),
+                    Err(ConfigError::TypeMismatch { .. })
+                ),
+                "{}",
+                option
+            );
+        }
+        assert!(matches!(
+            schema.check_type::<bool>("general:border_size"),
+            Err(ConfigError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!("1.5, -2".parse::<Vec2>().unwrap(), Vec2::new(1.5, -2.0));
+        assert!("1 2 3".parse::<Vec2>().is_err());
+
+        let gradient: Gradient = "ee33ccff ee00ff99 45deg".parse().unwrap();
+        assert_eq!(
+            gradient,
+            Gradient::new(
+                vec![
+                    ColorArgument::new(0x33, 0xcc, 0xff, 0xee),
+                    ColorArgument::new(0x00, 0xff, 0x99, 0xee)
+                ],
+                Some(45.0)
+            )
+        );
+        assert_eq!(gradient.to_string(), "rgba(33ccffee) rgba(00ff99ee) 45deg");
+        assert!("45deg ffffffff".parse::<Gradient>().is_err());
+
+        assert_eq!("5".parse::<CssGap>().unwrap(), CssGap::uniform(5));
+        assert_eq!("5 10".parse::<CssGap>().unwrap(), CssGap::new(5, 10, 5, 10));
+        assert_eq!(
+            "5,10,15".parse::<CssGap>().unwrap(),
+            CssGap::new(5, 10, 15, 10)
+        );
+        assert_eq!(CssGap::new(5, 10, 15, 20).to_string(), "5 10 15 20");
+        assert!("1 2 3 4 5".parse::<CssGap>().is_err());
+        assert!("".parse::<CssGap>().is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_get_and_set_options() {
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply(
+            "getoption general:border_size",
+            r#"{"option": "general:border_size", "int": 2, "set": true}"#,
+        );
+        mock.reply(
+            "getoption misc:vfr",
+            r#"{"option": "misc:vfr", "int": 1, "set": false}"#,
+        );
+        mock.reply(
+            "getoption decoration:shadow:offset",
+            r#"{"option": "decoration:shadow:offset", "vec2": [4, 8], "set": true}"#,
+        );
+        mock.reply(
+            "getoption general:col.active_border",
+            r#"{"option": "general:col.active_border", "custom": "ffffffff 0deg", "set": false}"#,
+        );
+        mock.reply(
+            "getoption general:gaps_in",
+            r#"{"option": "general:gaps_in", "custom": "5 5 5 5", "set": true}"#,
+        );
+        mock.reply(
+            "getoption general:gaps_out",
+            r#"{"option": "general:gaps_out", "custom": "20 10 20 10", "set": true}"#,
+        );
+        mock.reply(
+            "getoption misc:background_color",
+            r#"{"option": "misc:background_color", "int": 4278190080, "set": false}"#,
+        );
+
+        let conn = mock.connection();
+        let schema = schema();
+        assert_eq!(
+            conn.get_option_sync::<i32>(&schema, "general:border_size")
+                .unwrap(),
+            2
+        );
+        assert!(conn.get_option_sync::<bool>(&schema, "misc:vfr").unwrap());
+        assert_eq!(
+            conn.get_option_sync::<Vec2>(&schema, "decoration:shadow:offset")
+                .unwrap(),
+            Vec2::new(4.0, 8.0)
+        );
+        assert_eq!(
+            conn.get_option_sync::<Gradient>(&schema, "general:col.active_border")
+                .unwrap()
+                .angle,
+            Some(0.0)
+        );
+        assert_eq!(
+            conn.get_option_sync::<CssGap>(&schema, "general:gaps_in")
+                .unwrap(),
+            CssGap::uniform(5)
+        );
+        assert_eq!(
+            conn.get_option_sync::<CssGap>(&schema, "general:gaps_out")
+                .unwrap(),
+            CssGap::new(20, 10, 20, 10)
+        );
+        assert_eq!(
+            conn.get_option_sync::<ColorArgument>(&schema, "misc:background_color")
+                .unwrap(),
+            ColorArgument::new(0, 0, 0, 255)
+        );
+        assert_eq!(mock.received().len(), 7);
+
+        // The declared type is checked before anything is sent
+        assert!(matches!(
+            conn.get_option_sync::<i32>(&schema, "general:gaps_in"),
+            Err(ConfigError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            conn.get_option_sync::<f32>(&schema, "general:border_size"),
+            Err(ConfigError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            conn.get_option_sync::<i32>(&schema, "general:foo"),
+            Err(ConfigError::UnknownOption(_))
+        ));
+        assert_eq!(mock.received().len(), 7);
+
+        conn.set_option_sync(&schema, "general:border_size", &3)
+            .unwrap();
+        conn.set_option_sync(&schema, "decoration:shadow:offset", &Vec2::new(2.0, 2.0))
+            .unwrap();
+        conn.set_option_sync(&schema, "general:gaps_out", &CssGap::uniform(10))
+            .unwrap();
+        conn.set_option_sync(&schema, "general:gaps_out", &CssGap::new(10, 5, 10, 5))
+            .unwrap();
+        assert!(conn
+            .set_option_sync(&schema, "decoration:active_opacity", &1.5)
+            .is_err());
+        assert!(matches!(
+            conn.set_option_sync(&schema, "general:gaps_out", &10),
+            Err(ConfigError::TypeMismatch { .. })
+        ));
+        assert_eq!(
+            mock.received()[7..],
+            [
+                "keyword general:border_size 3",
+                "keyword decoration:shadow:offset 2 2",
+                "keyword general:gaps_out 10 10 10 10",
+                "keyword general:gaps_out 10 5 10 5"
+            ]
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_config_override() {
+        use super::ConfigOverride;
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply(
+            "getoption general:border_size",
+            r#"{"option": "general:border_size", "int": 2, "set": true}"#,
+        );
+        mock.reply(
+            "getoption general:col.active_border",
+            r#"{"option": "general:col.active_border", "custom": "ffffffff 0deg", "set": false}"#,
+        );
+        mock.reply(
+            "getoption decoration:shadow:offset",
+            r#"{"option": "decoration:shadow:offset", "vec2": [4, 8], "set": true}"#,
+        );
+        mock.reply("getoption general:foo", "no such option");
+        mock.reply("keyword decoration:shadow:offset 1", "invalid value");
+
+        let conn = mock.connection();
+        {
+            let outer = ConfigOverride::new_sync(
+                &conn,
+                [
+                    ("general:border_size", "5"),
+                    ("general:col.active_border", "rgb(ff0000)"),
+                ],
+            )
+            .unwrap();
+            assert_eq!(
+                outer.originals()[1],
+                (
+                    "general:col.active_border".to_string(),
+                    "rgba(ffffffff) 0deg".to_string()
+                )
+            );
+            let _inner =
+                ConfigOverride::new_sync(&conn, [("decoration:shadow:offset", "0 0")]).unwrap();
+        }
+        assert_eq!(
+            mock.received(),
+            [
+                "getoption general:border_size",
+                "getoption general:col.active_border",
+                "keyword general:border_size 5",
+                "keyword general:col.active_border rgb(ff0000)",
+                "getoption decoration:shadow:offset",
+                "keyword decoration:shadow:offset 0 0",
+                "keyword decoration:shadow:offset 4 8",
+                "keyword general:border_size 2",
+                "keyword general:col.active_border rgba(ffffffff) 0deg",
+            ]
+        );
+
+        mock.clear_received();
+        ConfigOverride::new_sync(&conn, [("general:border_size", "1")])
+            .unwrap()
+            .revert_sync()
+            .unwrap();
+        assert!(matches!(
+            ConfigOverride::new_sync(&conn, [("general:foo", "1")]),
+            Err(ConfigError::UnknownOption(_))
+        ));
+        assert!(matches!(
+            ConfigOverride::new_sync(&conn, [("decoration:shadow:offset", "1")]),
+            Err(ConfigError::CommandError(_))
+        ));
+        assert_eq!(
+            mock.received(),
+            [
+                "getoption general:border_size",
+                "keyword general:border_size 1",
+                "keyword general:border_size 2",
+                "getoption general:foo",
+                "getoption decoration:shadow:offset",
+                "keyword decoration:shadow:offset 1",
+                "keyword decoration:shadow:offset 4 8",
+            ]
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_config_override_dropped_in_runtime() {
+        use super::ConfigOverride;
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply(
+            "getoption general:border_size",
+            r#"{"option": "general:border_size", "int": 2, "set": true}"#,
+        );
+
+        let conn = mock.connection();
+        drop(
+            ConfigOverride::new(&conn, [("general:border_size", "5")])
+                .await
+                .unwrap(),
+        );
+        assert_eq!(
+            mock.received(),
+            [
+                "getoption general:border_size",
+                "keyword general:border_size 5",
+                "keyword general:border_size 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_option_set_from_files() {
+        let json = OptionSet::from_json(
+            r#"{
+                "general": {"border_size": 2, "col.active_border": "rgb(ff0000)"},
+                "decoration:blur:enabled": false,
+                "decoration:active_opacity": 0.5
+            }"#,
+        )
+        .unwrap();
+        let built = OptionSet::new()
+            .with_option("general:border_size", &2)
+            .with_option("general:col.active_border", &"rgb(ff0000)".to_string())
+            .with_option("decoration:blur:enabled", &false)
+            .with_option("decoration:active_opacity", &0.5);
+        assert_eq!(json, built);
+
+        #[cfg(feature = "toml")]
+        assert_eq!(
+            OptionSet::from_toml(
+                r#"
+                decoration.active_opacity = 0.5
+
+                [general]
+                border_size = 2
+                "col.active_border" = "rgb(ff0000)"
+
+                [decoration.blur]
+                enabled = false
+                "#
+            )
+            .unwrap(),
+            built
+        );
+
+        assert!(OptionSet::from_json(r#"{"general:border_size": [1, 2]}"#).is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_apply_option_set() {
+        use super::{ConfigOverride, OptionChange};
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply(
+            "getoption general:border_size",
+            r#"{"option": "general:border_size", "int": 2, "set": true}"#,
+        );
+        mock.reply(
+            "getoption general:col.active_border",
+            r#"{"option": "general:col.active_border", "custom": "ffff0000 0deg", "set": false}"#,
+        );
+        mock.reply(
+            "getoption general:col.inactive_border",
+            r#"{"option": "general:col.inactive_border", "int": 4278190335, "set": false}"#,
+        );
+        mock.reply(
+            "getoption decoration:blur:enabled",
+            r#"{"option": "decoration:blur:enabled", "int": 1, "set": false}"#,
+        );
+        mock.reply(
+            "getoption decoration:active_opacity",
+            r#"{"option": "decoration:active_opacity", "float": 0.5, "set": false}"#,
+        );
+        mock.reply(
+            "getoption master:new_status",
+            r#"{"option": "master:new_status", "int": 1, "set": true}"#,
+        );
+
+        let set: OptionSet = [
+            ("general:border_size", "3"),
+            ("general:col.active_border", "rgb(ff0000)"),
+            ("general:col.inactive_border", "rgba(0000ffff)"),
+            ("decoration:blur:enabled", "off"),
+            ("decoration:active_opacity", "0.5"),
+            ("master:new_status", "slave"),
+        ]
+        .into_iter()
+        .collect();
+
+        let conn = mock.connection();
+        let changes = set.apply_sync(&conn, Some(&schema())).unwrap();
+        assert_eq!(
+            changes,
+            [
+                OptionChange {
+                    name: "decoration:blur:enabled".to_string(),
+                    old: "1".to_string(),
+                    new: "off".to_string()
+                },
+                OptionChange {
+                    name: "general:border_size".to_string(),
+                    old: "2".to_string(),
+                    new: "3".to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            mock.received()[6..],
+            [
+                "keyword decoration:blur:enabled off",
+                "keyword general:border_size 3"
+            ]
+        );
+
+        // Choices given by name can't be compared without the schema
+        let changes = set.diff_sync(&conn, None).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes[2],
+            OptionChange {
+                name: "master:new_status".to_string(),
+                old: "1".to_string(),
+                new: "slave".to_string()
+            }
+        );
+
+        mock.clear_received();
+        ConfigOverride::new_sync(&conn, &set).unwrap();
+        assert_eq!(
+            mock.received(),
+            [
+                "getoption decoration:active_opacity",
+                "getoption decoration:blur:enabled",
+                "getoption general:border_size",
+                "getoption general:col.active_border",
+                "getoption general:col.inactive_border",
+                "getoption master:new_status",
+                "keyword decoration:active_opacity 0.5",
+                "keyword decoration:blur:enabled off",
+                "keyword general:border_size 3",
+                "keyword general:col.active_border rgb(ff0000)",
+                "keyword general:col.inactive_border rgba(0000ffff)",
+                "keyword master:new_status slave",
+                "keyword decoration:active_opacity 0.5",
+                "keyword decoration:blur:enabled 1",
+                "keyword general:border_size 2",
+                "keyword general:col.active_border rgba(ff0000ff) 0deg",
+                "keyword general:col.inactive_border 4278190335",
+                "keyword master:new_status 1",
+            ]
+        );
+    }
+}
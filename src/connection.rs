@@ -1,7 +1,9 @@
 use nix::unistd::getuid;
 use std::env::VarError;
 use std::fs::read_dir;
-use std::{env, io, path::PathBuf};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use std::{env, io};
 
 #[cfg(feature = "async")]
 use tokio::task::AbortHandle;
@@ -11,6 +13,7 @@ use tokio::task::AbortHandle;
 #[derive(Debug)]
 pub struct HyprlandConnection {
     instance: String,
+    runtime_dir: Option<PathBuf>,
     #[cfg(feature = "async")]
     pub(crate) event_handle: Option<AbortHandle>,
 }
@@ -27,11 +30,36 @@ impl HyprlandConnection {
     pub fn new(instance: String) -> HyprlandConnection {
         HyprlandConnection {
             instance,
+            runtime_dir: None,
             #[cfg(feature = "async")]
             event_handle: None,
         }
     }
 
+    /// Creates a new instance whose sockets are looked up in `runtime_dir` instead of
+    /// `$XDG_RUNTIME_DIR/hypr`.
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn with_runtime_dir(instance: String, runtime_dir: PathBuf) -> HyprlandConnection {
+        HyprlandConnection {
+            runtime_dir: Some(runtime_dir),
+            ..HyprlandConnection::new(instance)
+        }
+    }
+
+    /// Returns a new connection to `instance` that looks for sockets in the same place as this one.
+    #[cfg(any(feature = "async", feature = "sync"))]
+    pub(crate) fn with_instance(&self, instance: String) -> HyprlandConnection {
+        HyprlandConnection {
+            runtime_dir: self.runtime_dir.clone(),
+            ..HyprlandConnection::new(instance)
+        }
+    }
+
+    /// Returns the instance signature this connection uses
+    pub fn instance(&self) -> &str {
+        &self.instance
+    }
+
     /// Returns the current Hyprland instance
     pub fn get_current_instance() -> Result<String, VarError> {
         env::var("HYPRLAND_INSTANCE_SIGNATURE")
@@ -66,8 +94,42 @@ impl HyprlandConnection {
             .collect())
     }
 
+    /// Works the same as [`get_instances`], but the instances are sorted by when their event
+    /// socket was created, the oldest first, and the ones without one come last. Indexes into it
+    /// stay the same when a newer instance starts.
+    ///
+    /// [`get_instances`]: #method.get_instances
+    pub fn get_instances_by_age() -> Result<Vec<String>, io::Error> {
+        let mut instances = instances_in(&HyprlandConnection::get_runtime_dir())?;
+        instances.sort_by(|(a_created, a), (b_created, b)| {
+            (a_created.is_none(), a_created, a).cmp(&(b_created.is_none(), b_created, b))
+        });
+        Ok(instances
+            .into_iter()
+            .map(|(_, instance)| instance)
+            .collect())
+    }
+
+    fn runtime_dir(&self) -> PathBuf {
+        self.runtime_dir
+            .clone()
+            .unwrap_or_else(HyprlandConnection::get_runtime_dir)
+    }
+
+    /// Returns the instance whose event socket was created last, which is the one most likely to
+    /// belong to a Hyprland that just restarted.
+    #[cfg(feature = "async")]
+    pub(crate) fn get_newest_instance(&self) -> Option<String> {
+        instances_in(&self.runtime_dir())
+            .ok()?
+            .into_iter()
+            .filter_map(|(created, instance)| Some((created?, instance)))
+            .max()
+            .map(|(_, instance)| instance)
+    }
+
     pub(crate) fn get_socket_path(&self, socket_name: &str) -> Result<PathBuf, io::Error> {
-        let mut path = HyprlandConnection::get_runtime_dir();
+        let mut path = self.runtime_dir();
         path.push(self.instance.clone());
         path.push(socket_name);
 
@@ -91,3 +153,19 @@ impl HyprlandConnection {
         Ok(self.get_socket_path(".socket.sock")?)
     }
 }
+
+/// Returns the instances in `runtime_dir` along with when their event socket was created.
+fn instances_in(runtime_dir: &Path) -> Result<Vec<(Option<SystemTime>, String)>, io::Error> {
+    Ok(read_dir(runtime_dir)?
+        .filter_map(|inst_dir| {
+            let inst_dir = inst_dir.ok()?;
+            let created = inst_dir
+                .path()
+                .join(".socket2.sock")
+                .metadata()
+                .and_then(|meta| meta.modified())
+                .ok();
+            Some((created, inst_dir.file_name().into_string().ok()?))
+        })
+        .collect())
+}
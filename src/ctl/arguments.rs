@@ -1,6 +1,8 @@
 use core::fmt;
+use std::str::FromStr;
 
-use super::data::{Monitor, Window, Workspace, WorkspaceBrief};
+use super::data::{Keyboard, Monitor, Window, Workspace, WorkspaceBrief};
+use crate::errors::ArgumentParseError;
 
 pub trait Argument {
     fn to_argument_string(&self) -> String;
@@ -38,12 +40,44 @@ impl Argument for WindowArgument {
     }
 }
 
+/// Parses the formats Hyprland accepts. Like Hyprland, a regex without a prefix matches the class.
+impl FromStr for WindowArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use WindowArgument::*;
+        let err = || ArgumentParseError::new(s, "window");
+        Ok(match s.split_once(':') {
+            Some(("class", class)) => Class(class.to_string()),
+            Some(("initialclass", initialclass)) => InitialClass(initialclass.to_string()),
+            Some(("title", title)) => Title(title.to_string()),
+            Some(("initialtitle", initialtitle)) => InitialTitle(initialtitle.to_string()),
+            Some(("tag", tag)) => Tag(tag.to_string()),
+            Some(("pid", pid)) => Pid(pid.parse().map_err(|_| err())?),
+            Some(("address", address)) => Address(address.to_string()),
+            _ => match s {
+                "activewindow" => ActiveWindow,
+                "floating" => Floating,
+                "tiled" => Tiled,
+                _ if !s.is_empty() && !s.contains(':') => Class(s.to_string()),
+                _ => return Err(err()),
+            },
+        })
+    }
+}
+
 impl From<Window> for WindowArgument {
     fn from(value: Window) -> Self {
         WindowArgument::Address(value.address)
     }
 }
 
+impl From<&Window> for WindowArgument {
+    fn from(value: &Window) -> Self {
+        WindowArgument::Address(value.address.clone())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum RelAbs {
     Relative(i32),
@@ -60,6 +94,21 @@ impl Argument for RelAbs {
     }
 }
 
+impl FromStr for RelAbs {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "relative or absolute id");
+        if let Some(id) = s.strip_prefix('~') {
+            Ok(RelAbs::Absolute(id.parse().map_err(|_| err())?))
+        } else if s.starts_with(['+', '-']) {
+            Ok(RelAbs::Relative(s.parse().map_err(|_| err())?))
+        } else {
+            Err(err())
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum WorkspaceArgument {
     ID(i64),
@@ -99,6 +148,41 @@ impl Argument for WorkspaceArgument {
     }
 }
 
+impl FromStr for WorkspaceArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use WorkspaceArgument::*;
+        let err = || ArgumentParseError::new(s, "workspace");
+        Ok(match s {
+            "previous" => Previous,
+            "previous_per_monitor" => PreviousPerMonitor,
+            "empty" => Empty,
+            "emptym" => EmptyOnMonitor,
+            "emptyn" => EmptyNext,
+            "emptymn" => EmptyNextOnMonitor,
+            "special" => Special(None),
+            _ => {
+                if let Some(name) = s.strip_prefix("special:") {
+                    Special(Some(name.to_string()))
+                } else if let Some(name) = s.strip_prefix("name:") {
+                    Name(name.to_string())
+                } else if s.starts_with(['+', '-']) {
+                    RelativeID(s.parse().map_err(|_| err())?)
+                } else if let Some(relabs) = s.strip_prefix('m') {
+                    WorkspaceOnMonitor(relabs.parse().map_err(|_| err())?)
+                } else if let Some(relabs) = s.strip_prefix('r') {
+                    WorkspaceOnMonitorWithEmpty(relabs.parse().map_err(|_| err())?)
+                } else if let Some(relabs) = s.strip_prefix('e') {
+                    OpenWorkspace(relabs.parse().map_err(|_| err())?)
+                } else {
+                    ID(s.parse().map_err(|_| err())?)
+                }
+            }
+        })
+    }
+}
+
 impl From<Workspace> for WorkspaceArgument {
     fn from(value: Workspace) -> Self {
         WorkspaceArgument::ID(value.id)
@@ -132,6 +216,21 @@ impl Argument for DirectionArgument {
     }
 }
 
+impl FromStr for DirectionArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use DirectionArgument::*;
+        match s {
+            "l" | "left" => Ok(Left),
+            "r" | "right" => Ok(Right),
+            "u" | "t" | "up" => Ok(Up),
+            "d" | "b" | "down" => Ok(Down),
+            _ => Err(ArgumentParseError::new(s, "direction")),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum MonitorArgument {
     Direction(DirectionArgument),
@@ -154,6 +253,29 @@ impl Argument for MonitorArgument {
     }
 }
 
+impl FromStr for MonitorArgument {
+    type Err = ArgumentParseError;
+
+    /// Anything that isn't a direction, an id or a relative id is taken as a monitor name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use MonitorArgument::*;
+        let err = || ArgumentParseError::new(s, "monitor");
+        if s.is_empty() {
+            Err(err())
+        } else if s == "current" {
+            Ok(Current)
+        } else if let Ok(direction) = s.parse() {
+            Ok(Direction(direction))
+        } else if s.starts_with(['+', '-']) {
+            Ok(Relative(s.parse().map_err(|_| err())?))
+        } else if let Ok(id) = s.parse() {
+            Ok(ID(id))
+        } else {
+            Ok(Name(s.to_string()))
+        }
+    }
+}
+
 impl From<Monitor> for MonitorArgument {
     fn from(value: Monitor) -> Self {
         MonitorArgument::ID(value.id)
@@ -176,6 +298,27 @@ impl fmt::Display for NumPercent {
     }
 }
 
+impl FromStr for NumPercent {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |_| ArgumentParseError::new(s, "number or percentage");
+        match s.strip_suffix('%') {
+            Some(percent) => Ok(NumPercent::Percent(percent.parse().map_err(err)?)),
+            None => Ok(NumPercent::Number(s.parse().map_err(err)?)),
+        }
+    }
+}
+
+impl NumPercent {
+    /// Returns the number or the percentage, without its unit
+    pub fn value(&self) -> i32 {
+        match self {
+            Self::Number(value) | Self::Percent(value) => *value,
+        }
+    }
+}
+
 impl From<i32> for NumPercent {
     fn from(value: i32) -> Self {
         NumPercent::Number(value)
@@ -197,6 +340,26 @@ impl Argument for ResizeArgument {
     }
 }
 
+impl FromStr for ResizeArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "size");
+        let mut parts = s.split_whitespace().peekable();
+        let exact = parts.next_if_eq(&"exact").is_some();
+        let (Some(w), Some(h), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(err());
+        };
+        let (w, h) = (w.parse().map_err(|_| err())?, h.parse().map_err(|_| err())?);
+
+        if exact {
+            Ok(Self::Exact(w, h))
+        } else {
+            Ok(Self::Relative(w, h))
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum FloatArgument {
     Relative(f32),
@@ -212,6 +375,18 @@ impl Argument for FloatArgument {
     }
 }
 
+impl FromStr for FloatArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |_| ArgumentParseError::new(s, "float");
+        match s.strip_prefix("exact ") {
+            Some(float) => Ok(Self::Exact(float.trim().parse().map_err(err)?)),
+            None => Ok(Self::Relative(s.parse().map_err(err)?)),
+        }
+    }
+}
+
 impl From<f32> for FloatArgument {
     /// Assumes you want FloatArgument::Exact
     fn from(value: f32) -> Self {
@@ -234,6 +409,18 @@ impl Argument for ZHeightArgument {
     }
 }
 
+impl FromStr for ZHeightArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top" => Ok(Self::Top),
+            "bottom" => Ok(Self::Bottom),
+            _ => Err(ArgumentParseError::new(s, "z height")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ModArgument {
     Shift,
@@ -263,6 +450,25 @@ impl Argument for ModArgument {
     }
 }
 
+impl FromStr for ModArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ModArgument::*;
+        match s.to_uppercase().as_str() {
+            "SHIFT" => Ok(Shift),
+            "CAPS" => Ok(Caps),
+            "CTRL" | "CONTROL" => Ok(Ctrl),
+            "ALT" => Ok(AltMod),
+            "MOD2" => Ok(Mod2),
+            "MOD3" => Ok(Mod3),
+            "SUPER" | "WIN" | "LOGO" | "MOD4" => Ok(Super),
+            "MOD5" => Ok(Mod5),
+            _ => Err(ArgumentParseError::new(s, "modifier")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum KeyArgument {
     Char(char),
@@ -280,6 +486,26 @@ impl Argument for KeyArgument {
     }
 }
 
+impl FromStr for KeyArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "key");
+        if let Some(code) = s.strip_prefix("code:") {
+            return Ok(KeyArgument::Code(code.parse().map_err(|_| err())?));
+        }
+        if let Some(code) = s.strip_prefix("mouse:") {
+            return Ok(KeyArgument::Mouse(code.parse().map_err(|_| err())?));
+        }
+
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(KeyArgument::Char(c)),
+            _ => Err(err()),
+        }
+    }
+}
+
 impl Argument for bool {
     fn to_argument_string(&self) -> String {
         match self {
@@ -345,6 +571,26 @@ impl Argument for CycleNextArguments {
     }
 }
 
+impl FromStr for CycleNextArguments {
+    type Err = ArgumentParseError;
+
+    /// Parses a space separated list of `visible`, `floating`, `tiled` and `hist`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = Self::with_all_off();
+        for option in s.split_whitespace() {
+            match option {
+                "visible" => options.visible = true,
+                "floating" => options.floating = true,
+                "tiled" => options.tiled = true,
+                "hist" => options.use_focus_history = true,
+                _ => return Err(ArgumentParseError::new(s, "cyclenext option list")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TagArgument {
     Set(String),
@@ -362,6 +608,22 @@ impl Argument for TagArgument {
     }
 }
 
+impl FromStr for TagArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(tag) = s.strip_prefix('+').filter(|tag| !tag.is_empty()) {
+            Ok(Self::Set(tag.to_string()))
+        } else if let Some(tag) = s.strip_prefix('-').filter(|tag| !tag.is_empty()) {
+            Ok(Self::Unset(tag.to_string()))
+        } else if !s.is_empty() {
+            Ok(Self::Toggle(s.to_string()))
+        } else {
+            Err(ArgumentParseError::new(s, "tag"))
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum CornerArgument {
     BottomLeft = 0,
@@ -376,19 +638,39 @@ impl Argument for CornerArgument {
     }
 }
 
+impl FromStr for CornerArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Self::BottomLeft),
+            "1" => Ok(Self::BottomRight),
+            "2" => Ok(Self::TopRight),
+            "3" => Ok(Self::TopLeft),
+            _ => Err(ArgumentParseError::new(s, "corner")),
+        }
+    }
+}
+
 impl Argument for i32 {
     fn to_argument_string(&self) -> String {
         self.to_string()
     }
 }
 
+impl Argument for f32 {
+    fn to_argument_string(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl Argument for String {
     fn to_argument_string(&self) -> String {
         self.clone()
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ColorArgument {
     hex_str: String,
 }
@@ -399,6 +681,24 @@ impl ColorArgument {
             hex_str: format!("rgba({:02x}{:02x}{:02x}{:02x})", r, g, b, a),
         }
     }
+
+    /// Parses a hex color like the ones used on the web: `#rrggbb` or `#rrggbbaa`. The `#` is
+    /// optional.
+    pub fn from_hex(hex: &str) -> Result<Self, ArgumentParseError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let byte = |i: usize| {
+            digits
+                .get(i * 2..i * 2 + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| ArgumentParseError::new(hex, "hex color"))
+        };
+
+        match digits.len() {
+            6 => Ok(Self::new(byte(0)?, byte(1)?, byte(2)?, 0xff)),
+            8 => Ok(Self::new(byte(0)?, byte(1)?, byte(2)?, byte(3)?)),
+            _ => Err(ArgumentParseError::new(hex, "hex color")),
+        }
+    }
 }
 
 impl Argument for ColorArgument {
@@ -407,6 +707,47 @@ impl Argument for ColorArgument {
     }
 }
 
+impl FromStr for ColorArgument {
+    type Err = ArgumentParseError;
+
+    /// Parses colors in the formats Hyprland accepts: `rgba(rrggbbaa)`, `rgb(rrggbb)` and
+    /// `0xaarrggbb`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "color");
+        let byte = |hex: &str, i: usize| {
+            hex.get(i * 2..i * 2 + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(err)
+        };
+
+        if let Some(hex) = s.strip_prefix("rgba(").and_then(|s| s.strip_suffix(')')) {
+            if hex.len() == 8 {
+                return Ok(Self::new(
+                    byte(hex, 0)?,
+                    byte(hex, 1)?,
+                    byte(hex, 2)?,
+                    byte(hex, 3)?,
+                ));
+            }
+        } else if let Some(hex) = s.strip_prefix("rgb(").and_then(|s| s.strip_suffix(')')) {
+            if hex.len() == 6 {
+                return Ok(Self::new(byte(hex, 0)?, byte(hex, 1)?, byte(hex, 2)?, 0xff));
+            }
+        } else if let Some(hex) = s.strip_prefix("0x") {
+            if hex.len() == 8 {
+                return Ok(Self::new(
+                    byte(hex, 1)?,
+                    byte(hex, 2)?,
+                    byte(hex, 3)?,
+                    byte(hex, 0)?,
+                ));
+            }
+        }
+
+        Err(err())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum NotifyIconArgument {
     NoIcon,
@@ -433,6 +774,25 @@ impl Argument for NotifyIconArgument {
     }
 }
 
+impl FromStr for NotifyIconArgument {
+    type Err = ArgumentParseError;
+
+    /// Accepts both the icon number and its lowercase name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-1" | "none" => Ok(Self::NoIcon),
+            "0" | "warning" => Ok(Self::Warning),
+            "1" | "info" => Ok(Self::Info),
+            "2" | "hint" => Ok(Self::Hint),
+            "3" | "error" => Ok(Self::Error),
+            "4" | "confused" => Ok(Self::Confused),
+            "5" | "ok" => Ok(Self::Ok),
+            _ => Err(ArgumentParseError::new(s, "notification icon")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum MonitorsDataArgument {
     All,
     JustActive,
@@ -447,6 +807,19 @@ impl Argument for MonitorsDataArgument {
     }
 }
 
+impl FromStr for MonitorsDataArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            "" => Ok(Self::JustActive),
+            _ => Err(ArgumentParseError::new(s, "monitors argument")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum ChangeGroupActiveArgument {
     Index(i32),
     Back,
@@ -463,6 +836,22 @@ impl Argument for ChangeGroupActiveArgument {
     }
 }
 
+impl FromStr for ChangeGroupActiveArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "b" => Ok(Self::Back),
+            "f" => Ok(Self::Forward),
+            _ => s
+                .parse()
+                .map(Self::Index)
+                .map_err(|_| ArgumentParseError::new(s, "group index")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum LockGroupArgument {
     Lock,
     Unlock,
@@ -479,6 +868,20 @@ impl Argument for LockGroupArgument {
     }
 }
 
+impl FromStr for LockGroupArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lock" => Ok(LockGroupArgument::Lock),
+            "unlock" => Ok(LockGroupArgument::Unlock),
+            "toggle" => Ok(LockGroupArgument::Toggle),
+            _ => Err(ArgumentParseError::new(s, "group lock state")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum BoolChangeArgument {
     On,
     Off,
@@ -494,3 +897,1072 @@ impl Argument for BoolChangeArgument {
         }
     }
 }
+
+impl FromStr for BoolChangeArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "on" => Ok(BoolChangeArgument::On),
+            "off" => Ok(BoolChangeArgument::Off),
+            "toggle" => Ok(BoolChangeArgument::Toggle),
+            _ => Err(ArgumentParseError::new(s, "on, off or toggle")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStateArgument {
+    Down,
+    Up,
+    Repeat,
+}
+
+impl Argument for KeyStateArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Down => "down",
+            Self::Up => "up",
+            Self::Repeat => "repeat",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for KeyStateArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "down" => Ok(Self::Down),
+            "up" => Ok(Self::Up),
+            "repeat" => Ok(Self::Repeat),
+            _ => Err(ArgumentParseError::new(s, "key state")),
+        }
+    }
+}
+
+/// An option toggled for the current workspace by [`toggle_workspace_option`].
+///
+/// [`toggle_workspace_option`]: crate::commands::toggle_workspace_option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceOptArgument {
+    AllFloat,
+    AllPseudo,
+}
+
+impl Argument for WorkspaceOptArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::AllFloat => "allfloat",
+            Self::AllPseudo => "allpseudo",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for WorkspaceOptArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allfloat" => Ok(Self::AllFloat),
+            "allpseudo" => Ok(Self::AllPseudo),
+            _ => Err(ArgumentParseError::new(s, "workspace option")),
+        }
+    }
+}
+
+/// The action of a mouse bind, see [`mouse`].
+///
+/// [`mouse`]: crate::commands::mouse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseActionArgument {
+    MoveWindow,
+    ResizeWindow,
+}
+
+impl Argument for MouseActionArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::MoveWindow => "movewindow",
+            Self::ResizeWindow => "resizewindow",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for MouseActionArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "movewindow" => Ok(Self::MoveWindow),
+            "resizewindow" => Ok(Self::ResizeWindow),
+            _ => Err(ArgumentParseError::new(s, "mouse action")),
+        }
+    }
+}
+
+/// The mode of the `fullscreen` dispatcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullscreenModeArgument {
+    Fullscreen,
+    Maximize,
+}
+
+impl Argument for FullscreenModeArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Fullscreen => "0",
+            Self::Maximize => "1",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for FullscreenModeArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Self::Fullscreen),
+            "1" => Ok(Self::Maximize),
+            _ => Err(ArgumentParseError::new(s, "fullscreen mode")),
+        }
+    }
+}
+
+/// The window the `swapnext` dispatcher swaps with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapNextArgument {
+    Next,
+    Prev,
+}
+
+impl Argument for SwapNextArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Next => "next",
+            Self::Prev => "prev",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for SwapNextArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "next" => Ok(Self::Next),
+            "prev" => Ok(Self::Prev),
+            _ => Err(ArgumentParseError::new(s, "swap direction")),
+        }
+    }
+}
+
+/// The way the `movegroupwindow` dispatcher moves the window in its group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupWindowDirectionArgument {
+    Forward,
+    Back,
+}
+
+impl Argument for GroupWindowDirectionArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Forward => "f",
+            Self::Back => "b",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for GroupWindowDirectionArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f" | "forward" => Ok(Self::Forward),
+            "b" | "back" => Ok(Self::Back),
+            _ => Err(ArgumentParseError::new(s, "group window direction")),
+        }
+    }
+}
+
+/// The keyboard switched by [`switch_xkb_layout`].
+///
+/// [`switch_xkb_layout`]: crate::commands::switch_xkb_layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyboardArgument {
+    All,
+    /// The keyboard marked as `main` in [`Devices`]
+    ///
+    /// [`Devices`]: crate::data::Devices
+    Main,
+    Name(String),
+}
+
+impl KeyboardArgument {
+    /// Returns whether `keyboard` is one of the keyboards this argument refers to.
+    pub fn matches(&self, keyboard: &Keyboard) -> bool {
+        match self {
+            Self::All => true,
+            Self::Main => keyboard.main,
+            Self::Name(name) => &keyboard.name == name,
+        }
+    }
+}
+
+impl Argument for KeyboardArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::All => "all".to_string(),
+            Self::Main => "current".to_string(),
+            Self::Name(name) => name.clone(),
+        }
+    }
+}
+
+impl FromStr for KeyboardArgument {
+    type Err = ArgumentParseError;
+
+    /// Anything other than `all` and `current` is taken as a keyboard name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ArgumentParseError::new(s, "keyboard")),
+            "all" => Ok(Self::All),
+            "current" => Ok(Self::Main),
+            name => Ok(Self::Name(name.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XkbLayoutArgument {
+    Next,
+    Prev,
+    /// The index of the layout in [`Keyboard::layouts`]
+    Index(u32),
+}
+
+impl Argument for XkbLayoutArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Next => "next".to_string(),
+            Self::Prev => "prev".to_string(),
+            Self::Index(index) => index.to_string(),
+        }
+    }
+}
+
+impl FromStr for XkbLayoutArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "next" => Ok(Self::Next),
+            "prev" => Ok(Self::Prev),
+            _ => s
+                .parse()
+                .map(Self::Index)
+                .map_err(|_| ArgumentParseError::new(s, "layout")),
+        }
+    }
+}
+
+/// A POSIX signal, sent to Hyprland as its number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalArgument {
+    Hup,
+    Int,
+    Quit,
+    Kill,
+    Usr1,
+    Usr2,
+    Term,
+    Cont,
+    Stop,
+    Other(i32),
+}
+
+impl SignalArgument {
+    const NAMED: [(SignalArgument, &'static str, i32); 9] = [
+        (SignalArgument::Hup, "HUP", 1),
+        (SignalArgument::Int, "INT", 2),
+        (SignalArgument::Quit, "QUIT", 3),
+        (SignalArgument::Kill, "KILL", 9),
+        (SignalArgument::Usr1, "USR1", 10),
+        (SignalArgument::Usr2, "USR2", 12),
+        (SignalArgument::Term, "TERM", 15),
+        (SignalArgument::Cont, "CONT", 18),
+        (SignalArgument::Stop, "STOP", 19),
+    ];
+
+    /// Returns the signal number, as used on Linux
+    pub fn number(&self) -> i32 {
+        match self {
+            SignalArgument::Other(number) => *number,
+            signal => Self::NAMED
+                .iter()
+                .find(|(named, _, _)| named == signal)
+                .map(|(_, _, number)| *number)
+                .unwrap(),
+        }
+    }
+}
+
+impl Argument for SignalArgument {
+    fn to_argument_string(&self) -> String {
+        self.number().to_string()
+    }
+}
+
+impl FromStr for SignalArgument {
+    type Err = ArgumentParseError;
+
+    /// Accepts signal numbers and names, with or without the `SIG` prefix, ie. `15`, `term` or
+    /// `SIGTERM`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(number) = s.parse::<i32>() {
+            if number <= 0 {
+                return Err(ArgumentParseError::new(s, "signal"));
+            }
+            return Ok(Self::NAMED
+                .iter()
+                .find(|(_, _, n)| *n == number)
+                .map(|(signal, _, _)| *signal)
+                .unwrap_or(SignalArgument::Other(number)));
+        }
+
+        let upper = s.to_uppercase();
+        let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+        Self::NAMED
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(signal, _, _)| *signal)
+            .ok_or_else(|| ArgumentParseError::new(s, "signal"))
+    }
+}
+
+/// The backend used to create an output with [`create_output`].
+///
+/// [`create_output`]: crate::commands::create_output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputBackendArgument {
+    Auto,
+    Headless,
+    Wayland,
+    X11,
+}
+
+impl Argument for OutputBackendArgument {
+    fn to_argument_string(&self) -> String {
+        match self {
+            OutputBackendArgument::Auto => "auto",
+            OutputBackendArgument::Headless => "headless",
+            OutputBackendArgument::Wayland => "wayland",
+            OutputBackendArgument::X11 => "x11",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for OutputBackendArgument {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(OutputBackendArgument::Auto),
+            "headless" => Ok(OutputBackendArgument::Headless),
+            "wayland" => Ok(OutputBackendArgument::Wayland),
+            "x11" => Ok(OutputBackendArgument::X11),
+            _ => Err(ArgumentParseError::new(s, "output backend")),
+        }
+    }
+}
+
+/// A message for the dwindle layout, sent with [`dwindle_layout_message`].
+///
+/// [`dwindle_layout_message`]: crate::commands::dwindle_layout_message
+#[derive(Debug, Clone)]
+pub enum DwindleLayoutMessage {
+    ToggleSplit,
+    SwapSplit,
+    /// Picks the side of the focused window the next window opens on
+    Preselect(DirectionArgument),
+    /// Moves a window, the active one if `window` is `None`, to the root of its tree. Unstable
+    /// moves swap the two halves of the tree.
+    MoveToRoot {
+        window: Option<WindowArgument>,
+        unstable: bool,
+    },
+}
+
+impl Argument for DwindleLayoutMessage {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::ToggleSplit => "togglesplit".to_string(),
+            Self::SwapSplit => "swapsplit".to_string(),
+            Self::Preselect(direction) => format!("preselect {}", direction.to_argument_string()),
+            Self::MoveToRoot { window, unstable } => {
+                // The window can only be left out when it's the last argument
+                let window = match (window, unstable) {
+                    (Some(window), _) => format!(" {}", window.to_argument_string()),
+                    (None, true) => " activewindow".to_string(),
+                    (None, false) => String::new(),
+                };
+                format!(
+                    "movetoroot{}{}",
+                    window,
+                    if *unstable { " unstable" } else { "" }
+                )
+            }
+        }
+    }
+}
+
+impl FromStr for DwindleLayoutMessage {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "dwindle layout message");
+        let args: Vec<&str> = s.split_whitespace().collect();
+
+        match args.as_slice() {
+            ["togglesplit"] => Ok(Self::ToggleSplit),
+            ["swapsplit"] => Ok(Self::SwapSplit),
+            ["preselect", direction] => Ok(Self::Preselect(direction.parse()?)),
+            ["movetoroot"] => Ok(Self::MoveToRoot {
+                window: None,
+                unstable: false,
+            }),
+            ["movetoroot", window] => Ok(Self::MoveToRoot {
+                window: Some(window.parse()?),
+                unstable: false,
+            }),
+            ["movetoroot", window, "unstable"] => Ok(Self::MoveToRoot {
+                window: Some(window.parse()?),
+                unstable: true,
+            }),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// The window targeted by [`MasterLayoutMessage::SwapWithMaster`] and
+/// [`MasterLayoutMessage::FocusMaster`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasterTarget {
+    /// The master window
+    Master,
+    /// The first child window
+    Child,
+    /// The master window, or the first child if the master is focused
+    Auto,
+    /// The previously focused window, only valid with `focusmaster`
+    Previous,
+}
+
+impl Argument for MasterTarget {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Master => "master",
+            Self::Child => "child",
+            Self::Auto => "auto",
+            Self::Previous => "previous",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for MasterTarget {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "master" => Ok(Self::Master),
+            "child" => Ok(Self::Child),
+            "auto" => Ok(Self::Auto),
+            "previous" => Ok(Self::Previous),
+            _ => Err(ArgumentParseError::new(s, "master target")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasterOrientation {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+    Next,
+    Prev,
+    Cycle,
+}
+
+impl Argument for MasterOrientation {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Center => "center",
+            Self::Next => "next",
+            Self::Prev => "prev",
+            Self::Cycle => "cycle",
+        }
+        .to_string()
+    }
+}
+
+impl FromStr for MasterOrientation {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "top" => Ok(Self::Top),
+            "bottom" => Ok(Self::Bottom),
+            "center" => Ok(Self::Center),
+            "next" => Ok(Self::Next),
+            "prev" => Ok(Self::Prev),
+            "cycle" => Ok(Self::Cycle),
+            _ => Err(ArgumentParseError::new(s, "master orientation")),
+        }
+    }
+}
+
+/// A message for the master layout, sent with [`master_layout_message`].
+///
+/// [`master_layout_message`]: crate::commands::master_layout_message
+#[derive(Debug, Clone)]
+pub enum MasterLayoutMessage {
+    /// Swaps the focused window with `target`. With `ignore_master`, nothing happens when the
+    /// master is focused.
+    SwapWithMaster {
+        target: Option<MasterTarget>,
+        ignore_master: bool,
+    },
+    FocusMaster(Option<MasterTarget>),
+    CycleNext {
+        no_loop: bool,
+    },
+    CyclePrev {
+        no_loop: bool,
+    },
+    SwapNext {
+        no_loop: bool,
+    },
+    SwapPrev {
+        no_loop: bool,
+    },
+    AddMaster,
+    RemoveMaster,
+    Orientation(MasterOrientation),
+    /// Changes the size of the master area. `value` is between 0 and 1 when `exact`, otherwise
+    /// it's added to the current size and is between -1 and 1.
+    Mfact {
+        value: f32,
+        exact: bool,
+    },
+    RollNext,
+    RollPrev,
+}
+
+impl MasterLayoutMessage {
+    /// Checks that Hyprland accepts the message. `previous` is only a target for `focusmaster`,
+    /// `child` only for `swapwithmaster`, and `mfact` has to be in range.
+    pub fn validate(&self) -> Result<(), ArgumentParseError> {
+        let err = || {
+            Err(ArgumentParseError::new(
+                &self.to_argument_string(),
+                "master layout message",
+            ))
+        };
+
+        match self {
+            Self::SwapWithMaster {
+                target: Some(MasterTarget::Previous),
+                ..
+            } => err(),
+            Self::FocusMaster(Some(MasterTarget::Child)) => err(),
+            Self::Mfact { value, exact: true } if !(0.0..=1.0).contains(value) => err(),
+            Self::Mfact {
+                value,
+                exact: false,
+            } if !(-1.0..=1.0).contains(value) => err(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Argument for MasterLayoutMessage {
+    fn to_argument_string(&self) -> String {
+        let no_loop = |name: &str, no_loop: &bool| {
+            format!("{}{}", name, if *no_loop { " noloop" } else { "" })
+        };
+
+        match self {
+            Self::SwapWithMaster {
+                target,
+                ignore_master,
+            } => {
+                // `ignoremaster` is only read as the second argument
+                let target = match (target, ignore_master) {
+                    (Some(target), _) => format!(" {}", target.to_argument_string()),
+                    (None, true) => " auto".to_string(),
+                    (None, false) => String::new(),
+                };
+                format!(
+                    "swapwithmaster{}{}",
+                    target,
+                    if *ignore_master { " ignoremaster" } else { "" }
+                )
+            }
+            Self::FocusMaster(None) => "focusmaster".to_string(),
+            Self::FocusMaster(Some(target)) => {
+                format!("focusmaster {}", target.to_argument_string())
+            }
+            Self::CycleNext { no_loop: nl } => no_loop("cyclenext", nl),
+            Self::CyclePrev { no_loop: nl } => no_loop("cycleprev", nl),
+            Self::SwapNext { no_loop: nl } => no_loop("swapnext", nl),
+            Self::SwapPrev { no_loop: nl } => no_loop("swapprev", nl),
+            Self::AddMaster => "addmaster".to_string(),
+            Self::RemoveMaster => "removemaster".to_string(),
+            Self::Orientation(orientation) => {
+                format!("orientation{}", orientation.to_argument_string())
+            }
+            Self::Mfact { value, exact: true } => format!("mfact exact {}", value),
+            Self::Mfact {
+                value,
+                exact: false,
+            } => format!("mfact {}", value),
+            Self::RollNext => "rollnext".to_string(),
+            Self::RollPrev => "rollprev".to_string(),
+        }
+    }
+}
+
+impl FromStr for MasterLayoutMessage {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ArgumentParseError::new(s, "master layout message");
+        let args: Vec<&str> = s.split_whitespace().collect();
+        let no_loop = |args: &[&str]| match args {
+            [] => Ok(false),
+            ["noloop"] => Ok(true),
+            ["loop"] => Ok(false),
+            _ => Err(err()),
+        };
+        let mfact = |value: &str| value.parse::<f32>().map_err(|_| err());
+
+        let message = match args.as_slice() {
+            ["swapwithmaster", rest @ ..] => {
+                let (target, ignore_master) = match rest {
+                    [] => (None, false),
+                    ["ignoremaster"] => (None, true),
+                    [target] => (Some(target.parse()?), false),
+                    [target, "ignoremaster"] => (Some(target.parse()?), true),
+                    _ => return Err(err()),
+                };
+                Self::SwapWithMaster {
+                    target,
+                    ignore_master,
+                }
+            }
+            ["focusmaster"] => Self::FocusMaster(None),
+            ["focusmaster", target] => Self::FocusMaster(Some(target.parse()?)),
+            ["cyclenext", rest @ ..] => Self::CycleNext {
+                no_loop: no_loop(rest)?,
+            },
+            ["cycleprev", rest @ ..] => Self::CyclePrev {
+                no_loop: no_loop(rest)?,
+            },
+            ["swapnext", rest @ ..] => Self::SwapNext {
+                no_loop: no_loop(rest)?,
+            },
+            ["swapprev", rest @ ..] => Self::SwapPrev {
+                no_loop: no_loop(rest)?,
+            },
+            ["addmaster"] => Self::AddMaster,
+            ["removemaster"] => Self::RemoveMaster,
+            ["mfact", "exact", value] => Self::Mfact {
+                value: mfact(value)?,
+                exact: true,
+            },
+            ["mfact", value] => Self::Mfact {
+                value: mfact(value)?,
+                exact: false,
+            },
+            ["rollnext"] => Self::RollNext,
+            ["rollprev"] => Self::RollPrev,
+            [name] => match name.strip_prefix("orientation") {
+                Some(orientation) => Self::Orientation(orientation.parse().map_err(|_| err())?),
+                None => return Err(err()),
+            },
+            _ => return Err(err()),
+        };
+
+        message.validate()?;
+        Ok(message)
+    }
+}
+
+/// A message sent with `layoutmsg`, which depends on the layout in use.
+#[derive(Debug, Clone)]
+pub enum LayoutMessage {
+    Dwindle(DwindleLayoutMessage),
+    Master(MasterLayoutMessage),
+    /// A message for any other layout, ie. one added by a plugin
+    Other(String),
+}
+
+impl Argument for LayoutMessage {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Dwindle(message) => message.to_argument_string(),
+            Self::Master(message) => message.to_argument_string(),
+            Self::Other(message) => message.clone(),
+        }
+    }
+}
+
+impl FromStr for LayoutMessage {
+    type Err = ArgumentParseError;
+
+    /// Parses a message of the dwindle or the master layout, any other message ends up in
+    /// [`LayoutMessage::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ArgumentParseError::new(s, "layout message"));
+        }
+
+        if let Ok(message) = s.parse() {
+            Ok(Self::Dwindle(message))
+        } else if let Ok(message) = s.parse() {
+            Ok(Self::Master(message))
+        } else {
+            Ok(Self::Other(s.to_string()))
+        }
+    }
+}
+
+/// A window rule applied to the windows opened by [`exec`].
+///
+/// [`exec`]: crate::commands::exec
+#[derive(Debug, Clone)]
+pub enum ExecRule {
+    Workspace {
+        workspace: WorkspaceArgument,
+        silent: bool,
+    },
+    Monitor(MonitorArgument),
+    Float,
+    Tile,
+    Fullscreen,
+    Maximize,
+    Pin,
+    Center,
+    NoInitialFocus,
+    Size(NumPercent, NumPercent),
+    Move(NumPercent, NumPercent),
+    /// An opacity between 0 and 1
+    Opacity(f32),
+    /// Any other rule, written the way Hyprland expects it
+    Other(String),
+}
+
+impl ExecRule {
+    /// Checks that the rule can be sent to Hyprland. Rules are separated by `;` and enclosed in
+    /// brackets, none of which can be escaped.
+    pub fn validate(&self) -> Result<(), ArgumentParseError> {
+        let rule = self.to_argument_string();
+        let err = || Err(ArgumentParseError::new(&rule, "exec rule"));
+
+        if rule.trim().is_empty() || rule.contains([';', '[', ']']) {
+            return err();
+        }
+        match self {
+            Self::Opacity(opacity) if !(0.0..=1.0).contains(opacity) => err(),
+            Self::Size(w, h) if [w, h].iter().any(|size| size.value() < 0) => err(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Argument for ExecRule {
+    fn to_argument_string(&self) -> String {
+        match self {
+            Self::Workspace { workspace, silent } => format!(
+                "workspace {}{}",
+                workspace.to_argument_string(),
+                if *silent { " silent" } else { "" }
+            ),
+            Self::Monitor(monitor) => format!("monitor {}", monitor.to_argument_string()),
+            Self::Float => "float".to_string(),
+            Self::Tile => "tile".to_string(),
+            Self::Fullscreen => "fullscreen".to_string(),
+            Self::Maximize => "maximize".to_string(),
+            Self::Pin => "pin".to_string(),
+            Self::Center => "center".to_string(),
+            Self::NoInitialFocus => "noinitialfocus".to_string(),
+            Self::Size(w, h) => format!("size {} {}", w, h),
+            Self::Move(x, y) => format!("move {} {}", x, y),
+            Self::Opacity(opacity) => format!("opacity {}", opacity),
+            Self::Other(rule) => rule.clone(),
+        }
+    }
+}
+
+impl FromStr for ExecRule {
+    type Err = ArgumentParseError;
+
+    /// Parses a single rule. Rules this crate doesn't know about end up in [`ExecRule::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let err = || ArgumentParseError::new(s, "exec rule");
+        let args = s.split_whitespace().skip(1).collect::<Vec<&str>>();
+
+        let rule = match (s.split_whitespace().next(), args.as_slice()) {
+            (Some("workspace"), [workspace]) => Self::Workspace {
+                workspace: workspace.parse()?,
+                silent: false,
+            },
+            (Some("workspace"), [workspace, "silent"]) => Self::Workspace {
+                workspace: workspace.parse()?,
+                silent: true,
+            },
+            (Some("monitor"), [monitor]) => Self::Monitor(monitor.parse()?),
+            (Some("float"), []) => Self::Float,
+            (Some("tile"), []) => Self::Tile,
+            (Some("fullscreen"), []) => Self::Fullscreen,
+            (Some("maximize"), []) => Self::Maximize,
+            (Some("pin"), []) => Self::Pin,
+            (Some("center"), []) => Self::Center,
+            (Some("noinitialfocus"), []) => Self::NoInitialFocus,
+            (Some("size"), [w, h]) => Self::Size(w.parse()?, h.parse()?),
+            (Some("move"), [x, y]) => Self::Move(x.parse()?, y.parse()?),
+            (Some("opacity"), [opacity]) => Self::Opacity(opacity.parse().map_err(|_| err())?),
+            (Some(_), _) => Self::Other(s.to_string()),
+            (None, _) => return Err(err()),
+        };
+
+        rule.validate()?;
+        Ok(rule)
+    }
+}
+
+/// The rules passed to [`exec`]. See [`ExecRule`].
+///
+/// ```
+/// use hyprrust::commands::prelude::*;
+///
+/// let rules = ExecRules::new()
+///     .with_rule(ExecRule::Workspace {
+///         workspace: WorkspaceArgument::ID(3),
+///         silent: true,
+///     })
+///     .with_rule(ExecRule::Float)
+///     .with_rule(ExecRule::Size(800.into(), 600.into()));
+///
+/// let command = exec(rules, "kitty".to_string()).unwrap();
+/// assert_eq!(
+///     command.get_command(),
+///     "dispatch exec [workspace 3 silent; float; size 800 600] kitty"
+/// );
+/// ```
+///
+/// [`exec`]: crate::commands::exec
+#[derive(Debug, Clone, Default)]
+pub struct ExecRules(Vec<ExecRule>);
+
+impl ExecRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_rule(&mut self, rule: ExecRule) {
+        self.0.push(rule);
+    }
+
+    /// Builder variant of [`Self::add_rule`].
+    pub fn with_rule(mut self, rule: ExecRule) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    pub fn rules(&self) -> &[ExecRule] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Checks every rule, see [`ExecRule::validate`].
+    pub fn validate(&self) -> Result<(), ArgumentParseError> {
+        self.0.iter().try_for_each(ExecRule::validate)
+    }
+}
+
+impl Argument for ExecRules {
+    /// Returns the rules in brackets, or an empty string if there are no rules.
+    fn to_argument_string(&self) -> String {
+        if self.0.is_empty() {
+            return String::new();
+        }
+
+        let rules = self
+            .0
+            .iter()
+            .map(ExecRule::to_argument_string)
+            .collect::<Vec<String>>();
+        format!("[{}]", rules.join("; "))
+    }
+}
+
+impl FromStr for ExecRules {
+    type Err = ArgumentParseError;
+
+    /// Parses the rules in brackets, ie. `[float; size 800 600]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rules = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|rules| rules.strip_suffix(']'))
+            .ok_or_else(|| ArgumentParseError::new(s, "list of exec rules"))?;
+
+        Ok(ExecRules(
+            rules
+                .split(';')
+                .filter(|rule| !rule.trim().is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+impl From<Vec<ExecRule>> for ExecRules {
+    fn from(value: Vec<ExecRule>) -> Self {
+        ExecRules(value)
+    }
+}
+
+#[cfg(test)]
+mod arguments_tests {
+    use super::*;
+
+    fn round_trip<T: Argument + FromStr>(s: &str) -> String
+    where
+        T::Err: fmt::Debug,
+    {
+        s.parse::<T>().unwrap().to_argument_string()
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        for s in ["class:^kitty$", "address:0x1234", "pid:42", "activewindow"] {
+            assert_eq!(round_trip::<WindowArgument>(s), s);
+        }
+        for s in [
+            "3",
+            "-1",
+            "+2",
+            "m+1",
+            "r~2",
+            "e-1",
+            "name:web",
+            "special",
+            "special:a",
+        ] {
+            assert_eq!(round_trip::<WorkspaceArgument>(s), s);
+        }
+        for s in ["l", "DP-1", "2", "+1", "current"] {
+            assert_eq!(round_trip::<MonitorArgument>(s), s);
+        }
+        assert_eq!(round_trip::<ResizeArgument>("exact 50% 20"), "exact 50% 20");
+        assert_eq!(round_trip::<ResizeArgument>("-10 20"), "-10 20");
+        assert_eq!(round_trip::<KeyArgument>("code:36"), "code:36");
+        assert_eq!(round_trip::<TagArgument>("-work"), "-work");
+        assert_eq!(round_trip::<ModArgument>("super"), "SUPER");
+        assert_eq!(
+            round_trip::<ColorArgument>("0x80ff0000"),
+            round_trip::<ColorArgument>("rgba(ff000080)")
+        );
+        assert_eq!(
+            round_trip::<CycleNextArguments>("tiled hist"),
+            CycleNextArguments::new(true, false, false, true).to_argument_string()
+        );
+
+        assert_eq!(
+            round_trip::<ExecRules>("[workspace special:a silent;size 50% 600 ; custom rule]"),
+            "[workspace special:a silent; size 50% 600; custom rule]"
+        );
+
+        assert_eq!(round_trip::<SignalArgument>("SIGTERM"), "15");
+        assert_eq!(round_trip::<SignalArgument>("usr1"), "10");
+        assert_eq!(
+            "34".parse::<SignalArgument>(),
+            Ok(SignalArgument::Other(34))
+        );
+
+        for s in [
+            "movetoroot activewindow unstable",
+            "preselect l",
+            "togglesplit",
+        ] {
+            assert_eq!(round_trip::<DwindleLayoutMessage>(s), s);
+        }
+        for s in [
+            "swapwithmaster auto ignoremaster",
+            "focusmaster previous",
+            "cyclenext noloop",
+            "orientationcenter",
+            "mfact exact 0.6",
+            "mfact -0.05",
+        ] {
+            assert_eq!(round_trip::<MasterLayoutMessage>(s), s);
+        }
+
+        assert_eq!(round_trip::<WindowArgument>("kitty"), "class:kitty");
+        assert_eq!(round_trip::<WindowArgument>("^(kitty)$"), "class:^(kitty)$");
+        assert!("clas:kitty".parse::<WindowArgument>().is_err());
+        assert!("".parse::<WindowArgument>().is_err());
+        assert_eq!(
+            round_trip::<DwindleLayoutMessage>("movetoroot unstable"),
+            "movetoroot class:unstable"
+        );
+        assert!("movetoroot activewindow stable"
+            .parse::<DwindleLayoutMessage>()
+            .is_err());
+        assert!("swapwithmaster previous"
+            .parse::<MasterLayoutMessage>()
+            .is_err());
+        assert!("mfact exact 1.5".parse::<MasterLayoutMessage>().is_err());
+        assert!("orientationdiagonal"
+            .parse::<MasterLayoutMessage>()
+            .is_err());
+        assert!("SIGFOO".parse::<SignalArgument>().is_err());
+        assert!("m1".parse::<WorkspaceArgument>().is_err());
+        assert!("exact 1".parse::<ResizeArgument>().is_err());
+        assert!("rgb(fff)".parse::<ColorArgument>().is_err());
+        assert_eq!(
+            ColorArgument::from_hex("#ff000080")
+                .unwrap()
+                .to_argument_string(),
+            "rgba(ff000080)"
+        );
+        assert!(ColorArgument::from_hex("#fff").is_err());
+        assert_eq!(
+            "pid:x".parse::<WindowArgument>().unwrap_err().to_string(),
+            "\"pid:x\" is not a valid window"
+        );
+    }
+}
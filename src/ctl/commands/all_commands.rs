@@ -1,10 +1,13 @@
 #![allow(clippy::new_without_default)]
 
 use std::borrow::Cow;
+use std::str::FromStr;
 
 use super::*;
 use crate::ctl::data::FullscreenState;
+use crate::errors::ArgumentParseError;
 
+#[derive(Debug, Clone)]
 pub struct Command(Cow<'static, str>);
 impl Command {
     pub fn new(cmd: Cow<'static, str>) -> Self {
@@ -22,10 +25,10 @@ impl Command {
 /// use hyprrust::HyprlandConnection;
 /// use hyprrust::commands::prelude::*;
 ///
-/// make_command!(execr, "dispatch execr", cmd: String);
+/// make_command!(focus_class, "dispatch focuswindow", class: String);
 ///
 /// let conn = HyprlandConnection::current().unwrap();
-/// conn.send_command_sync(&execr("kitty".to_string())).unwrap();
+/// conn.send_command_sync(&focus_class("class:kitty".to_string())).unwrap();
 /// ```
 #[macro_export]
 macro_rules! make_command {
@@ -52,17 +55,38 @@ pub use make_command;
 // NOTE: For information on the commands, please visit the hyprland wiki
 // There are some slight variations, but the naming is approximately the same
 
-// TODO: implement exec
+/// Runs `program` through the shell, applying `rules` to the windows it opens.
+///
+/// Returns an error if a rule can't be sent to Hyprland (see [`ExecRule::validate`]), or if
+/// `program` is empty.
+pub fn exec(rules: ExecRules, program: String) -> Result<Command, ArgumentParseError> {
+    rules.validate()?;
+    let program = program.trim();
+    if program.is_empty() || (rules.is_empty() && program.starts_with('[')) {
+        return Err(ArgumentParseError::new(program, "program"));
+    }
+
+    let cmd = if rules.is_empty() {
+        format!("dispatch exec {}", program)
+    } else {
+        format!("dispatch exec {} {}", rules.to_argument_string(), program)
+    };
+    Ok(Command(Cow::Owned(cmd)))
+}
+
+// NOTE: Hyprland doesn't apply rules to execr
+make_command!(execr, "dispatch execr", program: String);
 make_command!(pass, "dispatch pass", to: WindowArgument);
-make_command!(send_shortcut, "dispatch sendshortcut", modifier: ModArgument, key: KeyArgument, window: WindowArgument);
+make_command!(send_shortcut, "dispatch sendshortcut", ",", modifier: ModArgument, key: KeyArgument, window: WindowArgument);
 make_command!(kill_active, "dispatch killactive");
 make_command!(force_kill_active, "dispatch forcekillactive");
 make_command!(close_window, "dispatch closewindow", which: WindowArgument);
 make_command!(kill_window, "dispatch killwindow", which: WindowArgument);
-// TODO: implement signal
+make_command!(signal_active, "dispatch signal", signal: SignalArgument);
+make_command!(signal_window, "dispatch signalwindow", ",", which: WindowArgument, signal: SignalArgument);
 make_command!(go_to_work_space, "dispatch workspace", to: WorkspaceArgument);
-make_command!(move_to_workspace, "dispatch movetoworkspace", to: WorkspaceArgument, which: WindowArgument);
-make_command!(move_to_workspace_silent, "dispatch movetoworkspacesilent", to: WorkspaceArgument, which: WindowArgument);
+make_command!(move_to_workspace, "dispatch movetoworkspace", ",", to: WorkspaceArgument, which: WindowArgument);
+make_command!(move_to_workspace_silent, "dispatch movetoworkspacesilent", ",", to: WorkspaceArgument, which: WindowArgument);
 make_command!(toggle_floating, "dispatch togglefloating", which: WindowArgument);
 make_command!(set_floating, "dispatch setfloating", which: WindowArgument);
 make_command!(set_tiled, "dispatch settiled", which: WindowArgument);
@@ -81,24 +105,39 @@ impl Argument for FullscreenState {
     }
 }
 
+impl FromStr for FullscreenState {
+    type Err = ArgumentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(FullscreenState::None),
+            "1" => Ok(FullscreenState::Maximized),
+            "2" => Ok(FullscreenState::Fullscreen),
+            "3" => Ok(FullscreenState::Max),
+            _ => Err(ArgumentParseError::new(s, "fullscreen state")),
+        }
+    }
+}
+
 make_command!(set_fullscreen_state, "dispatch fullscreenstate", internal: FullscreenState, client: FullscreenState);
-// TODO: implement dpms
+make_command!(dpms, "dispatch dpms", state: BoolChangeArgument);
+make_command!(dpms_monitor, "dispatch dpms", state: BoolChangeArgument, monitor: MonitorArgument);
 make_command!(pin_window, "dispatch pin", which: WindowArgument);
 make_command!(move_focus, "dispatch movefocus", to: DirectionArgument);
 
 pub fn move_window_in_direction(direction: DirectionArgument, silent: bool) -> Command {
     Command(Cow::Owned(format!(
-        "dispatch movewindow {} {}",
+        "dispatch movewindow {}{}",
         direction.to_argument_string(),
-        if silent { "silent" } else { "" }
+        if silent { " silent" } else { "" }
     )))
 }
 
 pub fn move_window_to_monitor(monitor: MonitorArgument, silent: bool) -> Command {
     Command(Cow::Owned(format!(
-        "dispatch movewindow {} {}",
+        "dispatch movewindow mon:{}{}",
         monitor.to_argument_string(),
-        if silent { "silent" } else { "" }
+        if silent { " silent" } else { "" }
     )))
 }
 
@@ -119,7 +158,7 @@ make_command!(move_cursor_to_corner, "dispatch movecursortocorner", which: Corne
 make_command!(move_cursor, "dispatch movecursor", x: i32, y: i32);
 make_command!(rename_workspace, "dispatch renameworkspace", which_id: String, new_name: String);
 make_command!(exit_hyprland, "dispatch exit");
-make_command!(force_render_reload, "dispatch forcerenderreload");
+make_command!(force_render_reload, "dispatch forcerendererreload");
 make_command!(move_current_workspace_to_monitor, "dispatch movecurrentworkspacetomonitor", to: MonitorArgument);
 make_command!(focus_workspace_on_current_monitor, "dispatch focusworkspaceoncurrentmonitor", which: WorkspaceArgument);
 make_command!(move_workspace_to_monitor, "dispatch moveworkspacetomonitor", which: WorkspaceArgument, to: MonitorArgument);
@@ -140,12 +179,168 @@ make_command!(set_ignore_group_lock, "dispatch setignoregrouplock", set: BoolCha
 make_command!(execute_global_shortcut, "dispatch global", which: String);
 make_command!(send_custom_event, "dispatch event", data: String);
 make_command!(set_property, "dispatch setprop", window: WindowArgument, name: String, value: String);
+
+/// Works like [`set_property`], but the value can't be changed by window rules afterwards.
+pub fn set_property_locked(window: WindowArgument, name: String, value: String) -> Command {
+    Command(Cow::Owned(format!(
+        "dispatch setprop {} {} {} lock",
+        window.to_argument_string(),
+        name,
+        value
+    )))
+}
+
 make_command!(toggle_swallow, "dispatch toggleswallow");
+make_command!(toggle_pseudo, "dispatch pseudo", which: WindowArgument);
+make_command!(layout_message, "dispatch layoutmsg", message: String);
+make_command!(toggle_split, "dispatch togglesplit");
+make_command!(swap_split, "dispatch swapsplit");
+make_command!(move_group_window_forward, "dispatch movegroupwindow");
+make_command!(move_group_window_back, "dispatch movegroupwindow b");
+make_command!(toggle_workspace_option, "dispatch workspaceopt", option: WorkspaceOptArgument);
+make_command!(focus_window_by_class, "dispatch focuswindowbyclass", class: String);
+make_command!(submap, "dispatch submap", name: String);
+make_command!(reset_submap, "dispatch submap reset");
+make_command!(send_key_state, "dispatch sendkeystate", ",", modifier: ModArgument, key: KeyArgument, state: KeyStateArgument, window: WindowArgument);
+make_command!(bring_active_to_top, "dispatch bringactivetotop");
+make_command!(force_idle, "dispatch forceidle", seconds: f32);
+// NOTE: `mouse` is what mouse binds send, `pressed` is whether the button is held down
+make_command!(mouse, "dispatch mouse", "", pressed: bool, action: MouseActionArgument);
+make_command!(dwindle_layout_message, "dispatch layoutmsg", message: DwindleLayoutMessage);
+
+/// Sends `message` to the master layout. Returns an error if Hyprland doesn't accept the message,
+/// see [`MasterLayoutMessage::validate`].
+pub fn master_layout_message(message: MasterLayoutMessage) -> Result<Command, ArgumentParseError> {
+    message.validate()?;
+    Ok(layout_message(message.to_argument_string()))
+}
 
-make_command!(set_config_value, "", variable: String, value: String);
+// Sends `keyword variable value`, the same request as `HyprlandConnection::set_config_value`
+make_command!(set_config_value, "keyword", variable: String, value: String);
 make_command!(reload_config, "reload");
 make_command!(kill_window_mode, "kill");
 make_command!(set_cursor_theme, "setcursor", theme_name: String, size: i32);
 make_command!(set_error, "seterror", color: ColorArgument, message: String);
+make_command!(disable_error, "seterror disable");
 make_command!(notify, "notify", icon: NotifyIconArgument, time_ms: i32, color: ColorArgument, message: String);
-// TODO: implement output, setxkblayout, dismissnotify
+make_command!(create_output, "output create", backend: OutputBackendArgument);
+make_command!(create_named_output, "output create", backend: OutputBackendArgument, name: String);
+make_command!(remove_output, "output remove", name: String);
+make_command!(load_plugin, "plugin load", path: String);
+make_command!(unload_plugin, "plugin unload", path: String);
+make_command!(switch_xkb_layout, "switchxkblayout", keyboard: KeyboardArgument, layout: XkbLayoutArgument);
+make_command!(dismiss_notifications, "dismissnotify", amount: i32);
+make_command!(dismiss_all_notifications, "dismissnotify");
+
+#[cfg(test)]
+mod all_commands_tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_validation() {
+        let rules = ExecRules::new().with_rule(ExecRule::Float);
+        assert!(exec(rules.clone(), "kitty".to_string()).is_ok());
+        assert!(exec(rules.clone(), " ".to_string()).is_err());
+        assert!(exec(ExecRules::new(), "[float] kitty".to_string()).is_err());
+        assert!(exec(
+            rules.with_rule(ExecRule::Other("float; pin".to_string())),
+            "kitty".to_string()
+        )
+        .is_err());
+        assert!(exec(
+            ExecRules::new().with_rule(ExecRule::Opacity(1.5)),
+            "kitty".to_string()
+        )
+        .is_err());
+        assert!(exec(
+            ExecRules::new().with_rule(ExecRule::Size((-1).into(), 20.into())),
+            "kitty".to_string()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_switch_xkb_layout() {
+        assert_eq!(
+            switch_xkb_layout(KeyboardArgument::Main, XkbLayoutArgument::Next).get_command(),
+            "switchxkblayout current next"
+        );
+        assert_eq!(
+            switch_xkb_layout(
+                KeyboardArgument::Name("yubikey".to_string()),
+                XkbLayoutArgument::Index(1)
+            )
+            .get_command(),
+            "switchxkblayout yubikey 1"
+        );
+    }
+
+    #[test]
+    fn test_layout_messages() {
+        assert_eq!(
+            dwindle_layout_message(DwindleLayoutMessage::Preselect(DirectionArgument::Up))
+                .get_command(),
+            "dispatch layoutmsg preselect u"
+        );
+        assert_eq!(
+            master_layout_message(MasterLayoutMessage::SwapWithMaster {
+                target: None,
+                ignore_master: true,
+            })
+            .unwrap()
+            .get_command(),
+            "dispatch layoutmsg swapwithmaster auto ignoremaster"
+        );
+        assert!(
+            master_layout_message(MasterLayoutMessage::FocusMaster(Some(MasterTarget::Child)))
+                .is_err()
+        );
+        assert!(master_layout_message(MasterLayoutMessage::Mfact {
+            value: -0.2,
+            exact: true,
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_dispatch_formats() {
+        assert_eq!(
+            send_shortcut(
+                ModArgument::Super,
+                KeyArgument::Char('q'),
+                WindowArgument::ActiveWindow
+            )
+            .get_command(),
+            "dispatch sendshortcut SUPER,q,activewindow"
+        );
+        assert_eq!(
+            move_to_workspace(WorkspaceArgument::ID(3), WindowArgument::ActiveWindow).get_command(),
+            "dispatch movetoworkspace 3,activewindow"
+        );
+        assert_eq!(
+            move_to_workspace_silent(WorkspaceArgument::ID(3), WindowArgument::ActiveWindow)
+                .get_command(),
+            "dispatch movetoworkspacesilent 3,activewindow"
+        );
+        assert_eq!(
+            move_window_to_monitor(MonitorArgument::Name("DP-1".to_string()), false).get_command(),
+            "dispatch movewindow mon:DP-1"
+        );
+        assert_eq!(
+            move_window_in_direction(DirectionArgument::Left, true).get_command(),
+            "dispatch movewindow l silent"
+        );
+        assert_eq!(
+            force_render_reload().get_command(),
+            "dispatch forcerendererreload"
+        );
+    }
+
+    #[test]
+    fn test_set_config_value() {
+        assert_eq!(
+            set_config_value("general:gaps_in".to_string(), "5".to_string()).get_command(),
+            "keyword general:gaps_in 5"
+        );
+    }
+}
@@ -0,0 +1,845 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use super::*;
+use crate::ctl::data::{Bind, FullscreenState};
+use crate::errors::{ArgumentParseError, CommandParseError};
+
+/// A type that can be used as a field of a [`Dispatcher`].
+trait DispatcherField: Sized {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError>;
+
+    /// The value used when the argument is missing, `None` if the argument is required.
+    fn missing() -> Option<Self> {
+        None
+    }
+
+    /// Returns the field formatted for Hyprland, `None` if it should be left out.
+    fn field_string(&self) -> Option<String>;
+}
+
+macro_rules! impl_dispatcher_field {
+    ($($type:ty),*) => {
+        $(impl DispatcherField for $type {
+            fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+                arg.parse()
+            }
+
+            fn field_string(&self) -> Option<String> {
+                Some(self.to_argument_string())
+            }
+        })*
+    };
+}
+
+impl_dispatcher_field!(
+    WindowArgument,
+    WorkspaceArgument,
+    DirectionArgument,
+    MonitorArgument,
+    ResizeArgument,
+    FloatArgument,
+    ZHeightArgument,
+    ModArgument,
+    KeyArgument,
+    CycleNextArguments,
+    TagArgument,
+    CornerArgument,
+    ChangeGroupActiveArgument,
+    LockGroupArgument,
+    BoolChangeArgument,
+    SignalArgument,
+    KeyStateArgument,
+    WorkspaceOptArgument,
+    FullscreenState,
+    FullscreenModeArgument,
+    SwapNextArgument,
+    GroupWindowDirectionArgument,
+    LayoutMessage
+);
+
+impl DispatcherField for String {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+        if arg.is_empty() {
+            return Err(ArgumentParseError::new(arg, "non empty string"));
+        }
+
+        Ok(arg.to_string())
+    }
+
+    fn field_string(&self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl DispatcherField for i32 {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+        arg.parse()
+            .map_err(|_| ArgumentParseError::new(arg, "integer"))
+    }
+
+    fn field_string(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl DispatcherField for i64 {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+        arg.parse()
+            .map_err(|_| ArgumentParseError::new(arg, "integer"))
+    }
+
+    fn field_string(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl DispatcherField for f32 {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+        arg.parse()
+            .map_err(|_| ArgumentParseError::new(arg, "float"))
+    }
+
+    fn field_string(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl DispatcherField for bool {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+        match arg {
+            "1" | "true" => Ok(true),
+            "0" | "false" => Ok(false),
+            _ => Err(ArgumentParseError::new(arg, "boolean")),
+        }
+    }
+
+    fn field_string(&self) -> Option<String> {
+        Some(self.to_argument_string())
+    }
+}
+
+impl<T: DispatcherField> DispatcherField for Option<T> {
+    fn parse_field(arg: &str) -> Result<Self, ArgumentParseError> {
+        T::parse_field(arg).map(Some)
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+
+    fn field_string(&self) -> Option<String> {
+        self.as_ref().and_then(T::field_string)
+    }
+}
+
+fn parse_field<T: DispatcherField>(
+    dispatcher: &str,
+    arg: Option<&str>,
+) -> Result<T, CommandParseError> {
+    match arg {
+        Some(arg) => T::parse_field(arg).map_err(|error| CommandParseError::InvalidArgument {
+            dispatcher: dispatcher.to_string(),
+            error,
+        }),
+        None => T::missing().ok_or_else(|| CommandParseError::MissingArgument {
+            dispatcher: dispatcher.to_string(),
+        }),
+    }
+}
+
+/// Splits `args` into at most `count` arguments, the last one gets the rest of the string.
+fn split_args<'a>(
+    dispatcher: &str,
+    args: &'a str,
+    separator: &str,
+    count: usize,
+) -> Result<Vec<&'a str>, CommandParseError> {
+    let args = args.trim();
+    if args.is_empty() {
+        return Ok(Vec::new());
+    }
+    if count == 0 {
+        return Err(CommandParseError::TooManyArguments {
+            dispatcher: dispatcher.to_string(),
+        });
+    }
+
+    Ok(args.splitn(count, separator).map(str::trim).collect())
+}
+
+macro_rules! separator {
+    () => {
+        " "
+    };
+    ($sep:literal) => {
+        $sep
+    };
+}
+
+// Dispatchers that don't fit `dispatchers!` and are parsed by hand
+const MOVE_WINDOW: &str = "movewindow";
+const EXEC: &str = "exec";
+const MOUSE: &str = "mouse";
+
+macro_rules! dispatchers {
+    ($(
+        $(#[$meta:meta])*
+        $variant:ident $({ $($field:ident: $type:ty),* $(,)? })? = $name:literal $(, $sep:literal)?;
+    )*) => {
+        /// A dispatcher with typed arguments. Unlike [`Command`], it can be inspected and changed,
+        /// and it can be parsed from the strings Hyprland uses, for example the dispatcher and
+        /// argument of a keybind.
+        ///
+        /// ```
+        /// use hyprrust::commands::prelude::*;
+        ///
+        /// let dispatcher: Dispatcher = "dispatch movetoworkspace 3,class:kitty".parse().unwrap();
+        /// match &dispatcher {
+        ///     Dispatcher::MoveToWorkspace { workspace, window } => {
+        ///         assert!(matches!(workspace, WorkspaceArgument::ID(3)));
+        ///         assert!(matches!(window, Some(WindowArgument::Class(class)) if class == "kitty"));
+        ///     }
+        ///     _ => unreachable!(),
+        /// }
+        ///
+        /// assert_eq!(
+        ///     dispatcher.to_command().get_command(),
+        ///     "dispatch movetoworkspace 3,class:kitty"
+        /// );
+        /// ```
+        #[derive(Debug, Clone)]
+        pub enum Dispatcher {
+            $($(#[$meta])* $variant $({ $($field: $type),* })?,)*
+            /// `movewindow` with a direction
+            MoveWindowInDirection { direction: DirectionArgument, silent: bool },
+            /// `movewindow` with `mon:` and a monitor
+            MoveWindowToMonitor { monitor: MonitorArgument, silent: bool },
+            /// `exec`, the rules are applied to the windows `program` opens
+            Exec { rules: ExecRules, program: String },
+            /// Sent by mouse binds, ie. `1movewindow` when the button is pressed. Binds only
+            /// have the action, so `pressed` is `None` for them.
+            Mouse { pressed: Option<bool>, action: MouseActionArgument },
+        }
+
+        impl Dispatcher {
+            /// The names of every dispatcher that can be represented
+            pub const NAMES: &'static [&'static str] = &[$($name,)* MOVE_WINDOW, EXEC, MOUSE];
+
+            /// Returns the name Hyprland uses for this dispatcher
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => $name,)*
+                    Self::MoveWindowInDirection { .. } | Self::MoveWindowToMonitor { .. } => {
+                        MOVE_WINDOW
+                    }
+                    Self::Exec { .. } => EXEC,
+                    Self::Mouse { .. } => MOUSE,
+                }
+            }
+
+            /// Returns the arguments of the dispatcher formatted for Hyprland
+            pub fn argument_string(&self) -> String {
+                match self {
+                    $(Self::$variant $({ $($field),* })? => {
+                        let fields: Vec<Option<String>> =
+                            vec![$($(DispatcherField::field_string($field)),*)?];
+                        fields
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<String>>()
+                            .join(separator!($($sep)?))
+                    })*
+                    Self::MoveWindowInDirection { direction, silent } => {
+                        format!(
+                            "{}{}",
+                            direction.to_argument_string(),
+                            if *silent { " silent" } else { "" }
+                        )
+                    }
+                    Self::MoveWindowToMonitor { monitor, silent } => {
+                        format!(
+                            "mon:{}{}",
+                            monitor.to_argument_string(),
+                            if *silent { " silent" } else { "" }
+                        )
+                    }
+                    Self::Exec { rules, program } if rules.is_empty() => program.clone(),
+                    Self::Exec { rules, program } => {
+                        format!("{} {}", rules.to_argument_string(), program)
+                    }
+                    Self::Mouse { pressed, action } => {
+                        format!(
+                            "{}{}",
+                            pressed.map(|pressed| pressed.to_argument_string()).unwrap_or_default(),
+                            action.to_argument_string()
+                        )
+                    }
+                }
+            }
+
+            /// Parses the dispatcher `name` with the arguments `args`, as they are found in a
+            /// keybind.
+            pub fn parse(name: &str, args: &str) -> Result<Self, CommandParseError> {
+                let name = name.trim();
+                match name {
+                    $($name => {
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = split_args(
+                            name,
+                            args,
+                            separator!($($sep)?),
+                            <[&str]>::len(&[$($(stringify!($field)),*)?]),
+                        )?
+                        .into_iter();
+
+                        Ok(Self::$variant $({ $($field: parse_field(name, args.next())?),* })?)
+                    })*
+                    MOVE_WINDOW => parse_move_window(args),
+                    EXEC => parse_exec(args),
+                    MOUSE => parse_mouse(args),
+                    _ => Err(CommandParseError::UnknownDispatcher(name.to_string())),
+                }
+            }
+        }
+    };
+}
+
+dispatchers! {
+    Pass { window: WindowArgument } = "pass";
+    SendShortcut {
+        modifier: ModArgument,
+        key: KeyArgument,
+        window: Option<WindowArgument>,
+    } = "sendshortcut", ",";
+    KillActive = "killactive";
+    ForceKillActive = "forcekillactive";
+    CloseWindow { window: WindowArgument } = "closewindow";
+    KillWindow { window: WindowArgument } = "killwindow";
+    Signal { signal: SignalArgument } = "signal";
+    SignalWindow { window: WindowArgument, signal: SignalArgument } = "signalwindow", ",";
+    Workspace { workspace: WorkspaceArgument } = "workspace";
+    MoveToWorkspace {
+        workspace: WorkspaceArgument,
+        window: Option<WindowArgument>,
+    } = "movetoworkspace", ",";
+    MoveToWorkspaceSilent {
+        workspace: WorkspaceArgument,
+        window: Option<WindowArgument>,
+    } = "movetoworkspacesilent", ",";
+    ToggleFloating { window: Option<WindowArgument> } = "togglefloating";
+    SetFloating { window: Option<WindowArgument> } = "setfloating";
+    SetTiled { window: Option<WindowArgument> } = "settiled";
+    Fullscreen { mode: Option<FullscreenModeArgument> } = "fullscreen";
+    FullscreenState { internal: FullscreenState, client: FullscreenState } = "fullscreenstate";
+    /// Applies to every monitor when `monitor` is `None`
+    Dpms { state: BoolChangeArgument, monitor: Option<MonitorArgument> } = "dpms";
+    Pin { window: Option<WindowArgument> } = "pin";
+    MoveFocus { direction: DirectionArgument } = "movefocus";
+    SwapWindow { direction: DirectionArgument } = "swapwindow";
+    CenterWindow { respect_reserved: Option<bool> } = "centerwindow";
+    ResizeActive { size: ResizeArgument } = "resizeactive";
+    MoveActive { by: ResizeArgument } = "moveactive";
+    ResizeWindowPixel { size: ResizeArgument, window: WindowArgument } = "resizewindowpixel", ",";
+    MoveWindowPixel { by: ResizeArgument, window: WindowArgument } = "movewindowpixel", ",";
+    CycleNext { options: Option<CycleNextArguments> } = "cyclenext";
+    SwapNext { direction: Option<SwapNextArgument> } = "swapnext";
+    TagWindow { tag: TagArgument, window: Option<WindowArgument> } = "tagwindow";
+    FocusWindow { window: WindowArgument } = "focuswindow";
+    FocusMonitor { monitor: MonitorArgument } = "focusmonitor";
+    SplitRatio { ratio: FloatArgument } = "splitratio";
+    MoveCursorToCorner { corner: CornerArgument } = "movecursortocorner";
+    MoveCursor { x: i32, y: i32 } = "movecursor";
+    RenameWorkspace { id: i64, name: Option<String> } = "renameworkspace";
+    Exit = "exit";
+    ForceRendererReload = "forcerendererreload";
+    MoveCurrentWorkspaceToMonitor { monitor: MonitorArgument } = "movecurrentworkspacetomonitor";
+    FocusWorkspaceOnCurrentMonitor {
+        workspace: WorkspaceArgument,
+    } = "focusworkspaceoncurrentmonitor";
+    MoveWorkspaceToMonitor {
+        workspace: WorkspaceArgument,
+        monitor: MonitorArgument,
+    } = "moveworkspacetomonitor";
+    SwapActiveWorkspaces { first: MonitorArgument, second: MonitorArgument } = "swapactiveworkspaces";
+    AlterZOrder { zheight: ZHeightArgument, window: Option<WindowArgument> } = "alterzorder", ",";
+    ToggleSpecialWorkspace { name: Option<String> } = "togglespecialworkspace";
+    FocusUrgentOrLast = "focusurgentorlast";
+    ToggleGroup = "togglegroup";
+    ChangeGroupActive { to: ChangeGroupActiveArgument } = "changegroupactive";
+    FocusCurrentOrLast = "focuscurrentorlast";
+    LockGroups { state: LockGroupArgument } = "lockgroups";
+    LockActiveGroup { state: LockGroupArgument } = "lockactivegroup";
+    MoveIntoGroup { direction: DirectionArgument } = "moveintogroup";
+    MoveOutOfGroup { window: Option<WindowArgument> } = "moveoutofgroup";
+    MoveWindowOrGroup { direction: DirectionArgument } = "movewindoworgroup";
+    DenyWindowFromGroup { state: BoolChangeArgument } = "denywindowfromgroup";
+    SetIgnoreGroupLock { state: BoolChangeArgument } = "setignoregrouplock";
+    Global { name: String } = "global";
+    Event { data: String } = "event";
+    SetProp { window: WindowArgument, property: String, value: String } = "setprop";
+    ToggleSwallow = "toggleswallow";
+    Pseudo { window: Option<WindowArgument> } = "pseudo";
+    LayoutMsg { message: LayoutMessage } = "layoutmsg";
+    ToggleSplit = "togglesplit";
+    SwapSplit = "swapsplit";
+    MoveGroupWindow { direction: Option<GroupWindowDirectionArgument> } = "movegroupwindow";
+    WorkspaceOpt { option: WorkspaceOptArgument } = "workspaceopt";
+    FocusWindowByClass { class: String } = "focuswindowbyclass";
+    /// `name` is `reset` to go back to the default submap
+    Submap { name: String } = "submap";
+    SendKeyState {
+        modifier: ModArgument,
+        key: KeyArgument,
+        state: KeyStateArgument,
+        window: WindowArgument,
+    } = "sendkeystate", ",";
+    BringActiveToTop = "bringactivetotop";
+    ForceIdle { seconds: f32 } = "forceidle";
+    /// `execr`, Hyprland doesn't apply rules to it
+    Execr { program: String } = "execr";
+}
+
+fn parse_move_window(args: &str) -> Result<Dispatcher, CommandParseError> {
+    let invalid = |error| CommandParseError::InvalidArgument {
+        dispatcher: MOVE_WINDOW.to_string(),
+        error,
+    };
+
+    let (target, silent) = match args.trim().split_once(char::is_whitespace) {
+        Some((target, "silent")) => (target, true),
+        Some(_) => {
+            return Err(CommandParseError::TooManyArguments {
+                dispatcher: MOVE_WINDOW.to_string(),
+            })
+        }
+        None => (args.trim(), false),
+    };
+
+    if target.is_empty() {
+        Err(CommandParseError::MissingArgument {
+            dispatcher: MOVE_WINDOW.to_string(),
+        })
+    } else if let Some(monitor) = target.strip_prefix("mon:") {
+        Ok(Dispatcher::MoveWindowToMonitor {
+            monitor: monitor.parse().map_err(invalid)?,
+            silent,
+        })
+    } else {
+        Ok(Dispatcher::MoveWindowInDirection {
+            direction: target.parse().map_err(invalid)?,
+            silent,
+        })
+    }
+}
+
+fn parse_exec(args: &str) -> Result<Dispatcher, CommandParseError> {
+    let args = args.trim();
+    let (rules, program) = match args.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        Some((rules, program)) => (format!("[{}]", rules).parse(), program),
+        None => (Ok(ExecRules::new()), args),
+    };
+    let invalid = |error| CommandParseError::InvalidArgument {
+        dispatcher: EXEC.to_string(),
+        error,
+    };
+
+    let rules = rules.map_err(invalid)?;
+    let program = program.trim().to_string();
+    if program.is_empty() {
+        return Err(CommandParseError::MissingArgument {
+            dispatcher: EXEC.to_string(),
+        });
+    }
+
+    // Validates the program the same way commands do
+    exec(rules.clone(), program.clone()).map_err(invalid)?;
+    Ok(Dispatcher::Exec { rules, program })
+}
+
+fn parse_mouse(args: &str) -> Result<Dispatcher, CommandParseError> {
+    let args = args.trim();
+    let (pressed, action) = match args.strip_prefix('1') {
+        Some(action) => (Some(true), action),
+        None => match args.strip_prefix('0') {
+            Some(action) => (Some(false), action),
+            None => (None, args),
+        },
+    };
+
+    if action.is_empty() {
+        return Err(CommandParseError::MissingArgument {
+            dispatcher: MOUSE.to_string(),
+        });
+    }
+
+    Ok(Dispatcher::Mouse {
+        pressed,
+        action: action
+            .parse()
+            .map_err(|error| CommandParseError::InvalidArgument {
+                dispatcher: MOUSE.to_string(),
+                error,
+            })?,
+    })
+}
+
+/// The flags that can follow `bind`, ie. `e` in `binde`.
+const BIND_FLAGS: &str = "lrenmtisdpo";
+
+impl Dispatcher {
+    /// Parses the dispatcher of a `bind` line from `hyprland.conf`, ie.
+    /// `bind = SUPER, 1, workspace, 1`. All `bind` variants are supported, including `bindd`
+    /// which has a description before the dispatcher, and `bindm` which only has the action of
+    /// the [mouse](Self::Mouse) dispatcher.
+    pub fn parse_bind_line(line: &str) -> Result<Self, CommandParseError> {
+        let not_a_bind = || CommandParseError::NotADispatch(line.to_string());
+        let (keyword, value) = line.split_once('=').ok_or_else(not_a_bind)?;
+        let flags = keyword.trim().strip_prefix("bind").ok_or_else(not_a_bind)?;
+        if !flags.chars().all(|flag| BIND_FLAGS.contains(flag)) {
+            return Err(not_a_bind());
+        }
+
+        // Modifiers, key and, for `bindd`, the description come before the dispatcher
+        let skip = if flags.contains('d') { 3 } else { 2 };
+        let mut parts = value.splitn(skip + 2, ',').skip(skip);
+        let name = parts.next().ok_or_else(not_a_bind)?;
+
+        if flags.contains('m') {
+            return parse_mouse(name);
+        }
+        Self::parse(name, parts.next().unwrap_or_default())
+    }
+
+    /// Returns the command that sends this dispatcher to Hyprland
+    pub fn to_command(&self) -> Command {
+        let args = self.argument_string();
+        if args.is_empty() {
+            Command::new(Cow::Owned(format!("dispatch {}", self.name())))
+        } else {
+            Command::new(Cow::Owned(format!("dispatch {} {}", self.name(), args)))
+        }
+    }
+}
+
+impl FromStr for Dispatcher {
+    type Err = CommandParseError;
+
+    /// Parses a dispatch command, ie. `dispatch workspace 3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dispatch = s
+            .trim_start()
+            .strip_prefix("dispatch")
+            .filter(|dispatch| dispatch.is_empty() || dispatch.starts_with(char::is_whitespace))
+            .ok_or_else(|| CommandParseError::NotADispatch(s.to_string()))?
+            .trim_start();
+
+        match dispatch.split_once(char::is_whitespace) {
+            Some((name, args)) => Self::parse(name, args),
+            None => Self::parse(dispatch, ""),
+        }
+    }
+}
+
+impl fmt::Display for Dispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_command().get_command())
+    }
+}
+
+impl From<&Dispatcher> for Command {
+    fn from(value: &Dispatcher) -> Self {
+        value.to_command()
+    }
+}
+
+impl From<Dispatcher> for Command {
+    fn from(value: Dispatcher) -> Self {
+        value.to_command()
+    }
+}
+
+impl TryFrom<&Command> for Dispatcher {
+    type Error = CommandParseError;
+
+    fn try_from(value: &Command) -> Result<Self, Self::Error> {
+        value.get_command().parse()
+    }
+}
+
+impl TryFrom<&Bind> for Dispatcher {
+    type Error = CommandParseError;
+
+    fn try_from(value: &Bind) -> Result<Self, Self::Error> {
+        Self::parse(&value.dispatcher, &value.arg)
+    }
+}
+
+#[cfg(test)]
+mod dispatcher_tests {
+    use super::*;
+
+    fn round_trip(command: Command) {
+        let dispatcher: Dispatcher = command.get_command().parse().unwrap();
+        assert_eq!(dispatcher.to_command().get_command(), command.get_command());
+    }
+
+    #[test]
+    fn test_dispatchers_match_commands() {
+        round_trip(kill_active());
+        round_trip(fullscreen_maximize());
+        round_trip(swap_with_prev());
+        round_trip(move_to_workspace(
+            WorkspaceArgument::Name("web".to_string()),
+            WindowArgument::Class("firefox".to_string()),
+        ));
+        round_trip(send_shortcut(
+            ModArgument::Super,
+            KeyArgument::Char('q'),
+            WindowArgument::ActiveWindow,
+        ));
+        round_trip(resize_window(
+            ResizeArgument::Exact(NumPercent::Percent(50), NumPercent::Number(300)),
+            WindowArgument::Pid(42),
+        ));
+        round_trip(move_window_in_direction(DirectionArgument::Left, true));
+        round_trip(move_window_to_monitor(MonitorArgument::Relative(1), false));
+        round_trip(set_property(
+            WindowArgument::ActiveWindow,
+            "alpha".to_string(),
+            "0.5 lock".to_string(),
+        ));
+        round_trip(center_window(true));
+        round_trip(execr("notify-send hi".to_string()));
+        round_trip(signal_window(
+            WindowArgument::Class("kitty".to_string()),
+            SignalArgument::Usr1,
+        ));
+        round_trip(dpms(BoolChangeArgument::Toggle));
+        round_trip(set_property_locked(
+            WindowArgument::ActiveWindow,
+            "bordersize".to_string(),
+            "0".to_string(),
+        ));
+        round_trip(send_key_state(
+            ModArgument::Ctrl,
+            KeyArgument::Char('c'),
+            KeyStateArgument::Down,
+            WindowArgument::Class("kitty".to_string()),
+        ));
+        round_trip(force_idle(2.5));
+        round_trip(move_group_window_back());
+        round_trip(mouse(true, MouseActionArgument::MoveWindow));
+        round_trip(dpms_monitor(
+            BoolChangeArgument::Off,
+            MonitorArgument::Name("DP-1".to_string()),
+        ));
+        round_trip(exec(ExecRules::new(), "kitty --hold".to_string()).unwrap());
+        round_trip(
+            exec(
+                ExecRules::new()
+                    .with_rule(ExecRule::Monitor(MonitorArgument::Name("DP-1".to_string())))
+                    .with_rule(ExecRule::Move(100.into(), NumPercent::Percent(10))),
+                "pavucontrol".to_string(),
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_parse_binds() {
+        let dispatcher =
+            Dispatcher::parse_bind_line("bind = SUPER, 3, movetoworkspace, 3").unwrap();
+        assert!(matches!(
+            dispatcher,
+            Dispatcher::MoveToWorkspace {
+                workspace: WorkspaceArgument::ID(3),
+                window: None
+            }
+        ));
+        let dispatcher =
+            Dispatcher::parse_bind_line("bindd = SUPER, Q, Close the window, killactive,").unwrap();
+        assert!(matches!(dispatcher, Dispatcher::KillActive));
+        let dispatcher = Dispatcher::parse("togglespecialworkspace", "").unwrap();
+        assert_eq!(dispatcher.to_string(), "dispatch togglespecialworkspace");
+
+        let dispatcher =
+            Dispatcher::parse_bind_line("bind = SUPER, Return, exec, [float;pin] kitty -e a,b")
+                .unwrap();
+        assert_eq!(
+            dispatcher.to_string(),
+            "dispatch exec [float; pin] kitty -e a,b"
+        );
+
+        let dispatcher =
+            Dispatcher::parse_bind_line("bindm = SUPER, mouse:272, movewindow").unwrap();
+        assert!(matches!(
+            dispatcher,
+            Dispatcher::Mouse {
+                pressed: None,
+                action: MouseActionArgument::MoveWindow
+            }
+        ));
+        assert_eq!(dispatcher.to_string(), "dispatch mouse movewindow");
+        assert!(
+            Dispatcher::parse_bind_line("binddm = SUPER, mouse:273, Resize, resizewindow").is_ok()
+        );
+        assert!(Dispatcher::parse_bind_line("bindel = , XF86AudioMute, exec, pamixer -t").is_ok());
+        assert_eq!(
+            Dispatcher::parse_bind_line("bindxyz = SUPER, Q, killactive").unwrap_err(),
+            CommandParseError::NotADispatch("bindxyz = SUPER, Q, killactive".to_string())
+        );
+    }
+
+    #[test]
+    fn test_typed_fields() {
+        assert!(matches!(
+            "dispatch swapnext prev".parse::<Dispatcher>().unwrap(),
+            Dispatcher::SwapNext {
+                direction: Some(SwapNextArgument::Prev)
+            }
+        ));
+        assert!(matches!(
+            "dispatch movegroupwindow back"
+                .parse::<Dispatcher>()
+                .unwrap(),
+            Dispatcher::MoveGroupWindow {
+                direction: Some(GroupWindowDirectionArgument::Back)
+            }
+        ));
+        assert!(matches!(
+            "dispatch fullscreen 1".parse::<Dispatcher>().unwrap(),
+            Dispatcher::Fullscreen {
+                mode: Some(FullscreenModeArgument::Maximize)
+            }
+        ));
+        assert!(matches!(
+            "dispatch mouse 0resizewindow"
+                .parse::<Dispatcher>()
+                .unwrap(),
+            Dispatcher::Mouse {
+                pressed: Some(false),
+                action: MouseActionArgument::ResizeWindow
+            }
+        ));
+        assert!(matches!(
+            "dispatch layoutmsg preselect l"
+                .parse::<Dispatcher>()
+                .unwrap(),
+            Dispatcher::LayoutMsg {
+                message: LayoutMessage::Dwindle(DwindleLayoutMessage::Preselect(
+                    DirectionArgument::Left
+                ))
+            }
+        ));
+        assert!(matches!(
+            "dispatch layoutmsg swapwithmaster child"
+                .parse::<Dispatcher>()
+                .unwrap(),
+            Dispatcher::LayoutMsg {
+                message: LayoutMessage::Master(_)
+            }
+        ));
+        assert!(matches!(
+            "dispatch layoutmsg scroller:cyclesize".parse::<Dispatcher>().unwrap(),
+            Dispatcher::LayoutMsg {
+                message: LayoutMessage::Other(message)
+            } if message == "scroller:cyclesize"
+        ));
+
+        for command in [
+            "dispatch swapnext up",
+            "dispatch movegroupwindow oops",
+            "dispatch fullscreen 5",
+            "dispatch mouse 1",
+            "dispatch mouse 1scroll",
+        ] {
+            assert!(
+                matches!(
+                    command.parse::<Dispatcher>(),
+                    Err(CommandParseError::InvalidArgument { .. }
+                        | CommandParseError::MissingArgument { .. })
+                ),
+                "{}",
+                command
+            );
+        }
+    }
+
+    #[test]
+    fn test_dispatcher_coverage() {
+        let hyprland: Vec<&str> = include_str!("hyprland_dispatchers.txt")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        let missing: Vec<&&str> = hyprland
+            .iter()
+            .filter(|name| !Dispatcher::NAMES.contains(name))
+            .collect();
+        let unknown: Vec<&&str> = Dispatcher::NAMES
+            .iter()
+            .filter(|name| !hyprland.contains(name))
+            .collect();
+        assert!(missing.is_empty(), "Missing dispatchers: {:?}", missing);
+        assert!(unknown.is_empty(), "Unknown dispatchers: {:?}", unknown);
+    }
+
+    #[test]
+    fn test_names_are_parsed() {
+        for name in Dispatcher::NAMES {
+            match Dispatcher::parse(name, "") {
+                Ok(dispatcher) => assert_eq!(dispatcher.name(), *name),
+                Err(CommandParseError::UnknownDispatcher(_)) => {
+                    panic!("{} is not parsed", name)
+                }
+                Err(_) => {}
+            }
+        }
+
+        for (name, args) in [
+            ("movewindow", "l silent"),
+            ("movewindow", "mon:DP-1"),
+            ("exec", "[float] kitty"),
+        ] {
+            let dispatcher = Dispatcher::parse(name, args).unwrap();
+            assert_eq!(dispatcher.name(), name);
+            assert_eq!(
+                dispatcher.to_string(),
+                format!("dispatch {} {}", name, args)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "keyword general:gaps_in 5"
+                .parse::<Dispatcher>()
+                .unwrap_err(),
+            CommandParseError::NotADispatch("keyword general:gaps_in 5".to_string())
+        );
+        assert_eq!(
+            "dispatch nope".parse::<Dispatcher>().unwrap_err(),
+            CommandParseError::UnknownDispatcher("nope".to_string())
+        );
+        assert!(matches!(
+            "dispatch focuswindow".parse::<Dispatcher>(),
+            Err(CommandParseError::MissingArgument { .. })
+        ));
+        assert!(matches!(
+            "dispatch killactive now".parse::<Dispatcher>(),
+            Err(CommandParseError::TooManyArguments { .. })
+        ));
+        assert!(matches!(
+            "dispatch movewindow mon:".parse::<Dispatcher>(),
+            Err(CommandParseError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            "dispatch movetoworkspace 3,pid:kitty".parse::<Dispatcher>(),
+            Err(CommandParseError::InvalidArgument { .. })
+        ));
+    }
+}
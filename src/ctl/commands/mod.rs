@@ -1,11 +1,16 @@
 use std::io;
 
 use crate::connection::HyprlandConnection;
-use crate::errors::{CommandError, HyprlandError};
+use crate::errors::CommandError;
 
 mod all_commands;
+mod dispatcher;
+mod recipe;
 use super::arguments::*;
 pub use all_commands::*;
+pub use dispatcher::Dispatcher;
+use recipe::get_recipe_outcomes;
+pub use recipe::{CommandOutcome, RecipeMode, RecipeReport};
 
 impl HyprlandConnection {
     /// Send a dispatch command to Hyprland.
@@ -15,41 +20,34 @@ impl HyprlandConnection {
     /// ```
     #[cfg(feature = "async")]
     pub async fn send_command(&self, command: &Command) -> Result<(), CommandError> {
-        check_hyprland_response(self.send_raw_message(command.get_command()).await)
+        check_hyprland_response(
+            command.get_command(),
+            self.send_raw_message(command.get_command()).await,
+        )
     }
 
     /// The blocking counterpart of [`Self::send_command`].
     #[cfg(feature = "sync")]
     pub fn send_command_sync(&self, command: &Command) -> Result<(), CommandError> {
-        check_hyprland_response(self.send_raw_message_sync(command.get_command()))
+        check_hyprland_response(
+            command.get_command(),
+            self.send_raw_message_sync(command.get_command()),
+        )
     }
 
     /// Sends a list of commands to the socket at once. This is faster than sending each command
-    /// separately.
+    /// separately. Use [`Self::send_recipe_with_report`] to find out which commands failed.
     #[cfg(feature = "async")]
     pub async fn send_recipe<'a, T>(&self, recipe: T) -> Result<(), Vec<CommandError>>
     where
         T: IntoIterator<Item = &'a Command>,
     {
+        let recipe = recipe.into_iter().collect::<Vec<&Command>>();
         let resp = self
-            .send_raw_message(get_batch_from_recipe(recipe).as_str())
+            .send_raw_message(get_batch_from_recipe(recipe.iter().copied()).as_str())
             .await;
 
-        match resp {
-            Ok(resp) => {
-                let errors = resp
-                    .split("\n\n\n")
-                    .filter(|resp| resp != &"ok")
-                    .map(|resp| CommandError::HyprlandError(HyprlandError::new(resp.to_string())))
-                    .collect::<Vec<CommandError>>();
-                if errors.is_empty() {
-                    Ok(())
-                } else {
-                    Err(errors)
-                }
-            }
-            Err(e) => Err(vec![CommandError::IOError(e)]),
-        }
+        check_recipe_response(&recipe, resp)
     }
 
     /// The blocking counterpart of [`Self::send_recipe`].
@@ -58,41 +56,42 @@ impl HyprlandConnection {
     where
         T: IntoIterator<Item = &'a Command>,
     {
-        let resp = self.send_raw_message_sync(get_batch_from_recipe(recipe).as_str());
-
-        match resp {
-            Ok(resp) => {
-                let errors = resp
-                    .split("\n\n\n")
-                    .filter(|resp| resp != &"ok")
-                    .map(|resp| CommandError::HyprlandError(HyprlandError::new(resp.to_string())))
-                    .collect::<Vec<CommandError>>();
-                if errors.is_empty() {
-                    Ok(())
-                } else {
-                    Err(errors)
-                }
-            }
-            Err(e) => Err(vec![CommandError::IOError(e)]),
-        }
+        let recipe = recipe.into_iter().collect::<Vec<&Command>>();
+        let resp =
+            self.send_raw_message_sync(get_batch_from_recipe(recipe.iter().copied()).as_str());
+
+        check_recipe_response(&recipe, resp)
     }
 
     /// Sets the config variable named `variable` to `value`. Can be used instead of
-    /// [`Self::send_command`] with [`set_config_value`].
+    /// [`Self::send_command`] with [`set_config_value`]. The value isn't validated, see
+    /// [`Self::set_option`].
     #[cfg(feature = "async")]
+    #[deprecated(note = "use `set_option`, which checks the value against the config schema")]
     pub async fn set_config_value(&self, variable: &str, value: &str) -> Result<(), CommandError> {
-        check_hyprland_response(
-            self.send_raw_message(format!("keyword {} {}", variable, value).as_str())
-                .await,
-        )
+        let command = format!("keyword {} {}", variable, value);
+        check_hyprland_response(&command, self.send_raw_message(&command).await)
     }
 
     /// Blocking variant of [`Self::set_config_value`].
     #[cfg(feature = "sync")]
+    #[deprecated(note = "use `set_option_sync`, which checks the value against the config schema")]
     pub fn set_config_value_sync(&self, variable: &str, value: &str) -> Result<(), CommandError> {
-        check_hyprland_response(
-            self.send_raw_message_sync(format!("keyword {} {}", variable, value).as_str()),
-        )
+        let command = format!("keyword {} {}", variable, value);
+        check_hyprland_response(&command, self.send_raw_message_sync(&command))
+    }
+
+    /// Sends `recipe` with a blocking request whatever features are enabled, for use in
+    /// destructors, which can't wait for a future. Errors reported by Hyprland are ignored.
+    #[cfg(any(feature = "async", feature = "sync"))]
+    pub(crate) fn send_recipe_blocking(&self, recipe: &[Command]) -> Result<(), io::Error> {
+        use std::io::{Read, Write};
+        use std::os::unix::net::UnixStream;
+
+        let mut socket = UnixStream::connect(self.get_ctl_socket_path()?)?;
+        socket.write_all(get_batch_from_recipe(recipe).as_bytes())?;
+        socket.read_to_string(&mut String::new())?;
+        Ok(())
     }
 }
 
@@ -109,10 +108,35 @@ where
     full_command
 }
 
-fn check_hyprland_response(resp: Result<String, io::Error>) -> Result<(), CommandError> {
+fn check_hyprland_response(
+    command: &str,
+    resp: Result<String, io::Error>,
+) -> Result<(), CommandError> {
     match resp {
-        Ok(resp) if resp == "ok" => Ok(()),
-        Ok(e) => Err(CommandError::HyprlandError(HyprlandError::new(e))),
+        Ok(resp) if resp.trim() == "ok" => Ok(()),
+        Ok(e) => Err(CommandError::from_reply(command, e)),
         Err(e) => Err(CommandError::IOError(e)),
     }
 }
+
+fn check_recipe_response(
+    recipe: &[&Command],
+    resp: Result<String, io::Error>,
+) -> Result<(), Vec<CommandError>> {
+    if let Err(e) = resp {
+        return Err(vec![CommandError::IOError(e)]);
+    }
+
+    let errors = get_recipe_outcomes(recipe, resp)
+        .into_iter()
+        .filter_map(|outcome| match outcome {
+            CommandOutcome::Failed(e) => Some(e),
+            _ => None,
+        })
+        .collect::<Vec<CommandError>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,264 @@
+use std::io;
+
+#[cfg(any(feature = "async", feature = "sync"))]
+use super::get_batch_from_recipe;
+use super::{check_hyprland_response, Command};
+use crate::connection::HyprlandConnection;
+use crate::errors::CommandError;
+
+/// Decides what happens to the rest of a recipe after one of its commands fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecipeMode {
+    /// Every command is sent, in a single batch, regardless of failures.
+    #[default]
+    Continue,
+    /// Commands are sent one by one and the first failure stops the recipe. This is slower than
+    /// [`RecipeMode::Continue`], since Hyprland always runs a batch to the end.
+    StopOnFirstError,
+}
+
+/// What happened to a single command of a recipe.
+#[derive(Debug)]
+pub enum CommandOutcome {
+    Succeeded,
+    Failed(CommandError),
+    /// The command was not sent because an earlier one failed.
+    Skipped,
+}
+
+impl CommandOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Succeeded)
+    }
+}
+
+/// Pairs every command of a recipe with its outcome, in the order they were given.
+#[derive(Debug)]
+pub struct RecipeReport<'a> {
+    outcomes: Vec<(&'a Command, CommandOutcome)>,
+}
+
+impl<'a> RecipeReport<'a> {
+    /// Returns every command and its outcome.
+    pub fn outcomes(&self) -> &[(&'a Command, CommandOutcome)] {
+        &self.outcomes
+    }
+
+    /// Returns whether every command succeeded.
+    pub fn is_success(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|(_, outcome)| outcome.is_success())
+    }
+
+    /// Returns the index, the command and the error of every command that failed.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &'a Command, &CommandError)> {
+        self.outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (command, outcome))| match outcome {
+                CommandOutcome::Failed(e) => Some((i, *command, e)),
+                _ => None,
+            })
+    }
+
+    /// Returns the index, the command and the error of the first command that failed.
+    pub fn first_failure(&self) -> Option<(usize, &'a Command, &CommandError)> {
+        self.failures().next()
+    }
+}
+
+impl<'a> IntoIterator for RecipeReport<'a> {
+    type Item = (&'a Command, CommandOutcome);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.outcomes.into_iter()
+    }
+}
+
+/// Splits the reply to a batch into the outcome of each command. If the batch could not be sent,
+/// every command is marked as failed.
+pub(crate) fn get_recipe_outcomes(
+    recipe: &[&Command],
+    resp: Result<String, io::Error>,
+) -> Vec<CommandOutcome> {
+    match resp {
+        Ok(resp) => {
+            let mut replies = resp.split("\n\n\n");
+            recipe
+                .iter()
+                .map(|command| {
+                    let reply = replies.next().unwrap_or_default().to_string();
+                    match check_hyprland_response(command.get_command(), Ok(reply)) {
+                        Ok(()) => CommandOutcome::Succeeded,
+                        Err(e) => CommandOutcome::Failed(e),
+                    }
+                })
+                .collect()
+        }
+        Err(e) => recipe
+            .iter()
+            .map(|_| {
+                CommandOutcome::Failed(CommandError::IOError(io::Error::new(
+                    e.kind(),
+                    e.to_string(),
+                )))
+            })
+            .collect(),
+    }
+}
+
+impl HyprlandConnection {
+    /// Works like [`Self::send_recipe`], but returns the outcome of every command in the recipe.
+    /// See [`RecipeMode`] for what happens after a command fails.
+    #[cfg(feature = "async")]
+    pub async fn send_recipe_with_report<'a, T>(
+        &self,
+        recipe: T,
+        mode: RecipeMode,
+    ) -> RecipeReport<'a>
+    where
+        T: IntoIterator<Item = &'a Command>,
+    {
+        let recipe = recipe.into_iter().collect::<Vec<&Command>>();
+
+        let outcomes = match mode {
+            RecipeMode::Continue => {
+                let resp = self
+                    .send_raw_message(get_batch_from_recipe(recipe.iter().copied()).as_str())
+                    .await;
+                get_recipe_outcomes(&recipe, resp)
+            }
+            RecipeMode::StopOnFirstError => {
+                let mut outcomes = Vec::with_capacity(recipe.len());
+                let mut failed = false;
+                for command in recipe.iter() {
+                    if failed {
+                        outcomes.push(CommandOutcome::Skipped);
+                        continue;
+                    }
+                    outcomes.push(match self.send_command(command).await {
+                        Ok(()) => CommandOutcome::Succeeded,
+                        Err(e) => {
+                            failed = true;
+                            CommandOutcome::Failed(e)
+                        }
+                    });
+                }
+                outcomes
+            }
+        };
+
+        RecipeReport {
+            outcomes: recipe.into_iter().zip(outcomes).collect(),
+        }
+    }
+
+    /// The blocking counterpart of [`Self::send_recipe_with_report`].
+    #[cfg(feature = "sync")]
+    pub fn send_recipe_with_report_sync<'a, T>(
+        &self,
+        recipe: T,
+        mode: RecipeMode,
+    ) -> RecipeReport<'a>
+    where
+        T: IntoIterator<Item = &'a Command>,
+    {
+        let recipe = recipe.into_iter().collect::<Vec<&Command>>();
+
+        let outcomes = match mode {
+            RecipeMode::Continue => {
+                let resp = self
+                    .send_raw_message_sync(get_batch_from_recipe(recipe.iter().copied()).as_str());
+                get_recipe_outcomes(&recipe, resp)
+            }
+            RecipeMode::StopOnFirstError => {
+                let mut outcomes = Vec::with_capacity(recipe.len());
+                let mut failed = false;
+                for command in recipe.iter() {
+                    if failed {
+                        outcomes.push(CommandOutcome::Skipped);
+                        continue;
+                    }
+                    outcomes.push(match self.send_command_sync(command) {
+                        Ok(()) => CommandOutcome::Succeeded,
+                        Err(e) => {
+                            failed = true;
+                            CommandOutcome::Failed(e)
+                        }
+                    });
+                }
+                outcomes
+            }
+        };
+
+        RecipeReport {
+            outcomes: recipe.into_iter().zip(outcomes).collect(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod recipe_tests {
+    use super::{CommandOutcome, RecipeMode};
+    use crate::commands::prelude::*;
+    use crate::errors::CommandError;
+    use crate::testing::MockHyprland;
+
+    fn recipe() -> [Command; 3] {
+        [
+            set_floating(WindowArgument::ActiveWindow),
+            focus_window(WindowArgument::Class("nope".to_string())),
+            pin_window(WindowArgument::ActiveWindow),
+        ]
+    }
+
+    #[test]
+    fn test_recipe_continue() {
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("dispatch focuswindow class:nope", "Window not found");
+
+        let recipe = recipe();
+        let report = mock
+            .connection()
+            .send_recipe_with_report_sync(&recipe, RecipeMode::Continue);
+
+        assert!(!report.is_success());
+        let outcomes = report.outcomes();
+        assert!(outcomes[0].1.is_success());
+        assert!(matches!(
+            outcomes[1].1,
+            CommandOutcome::Failed(CommandError::NoSuchWindow(_))
+        ));
+        assert!(outcomes[2].1.is_success());
+
+        let (index, command, _) = report.first_failure().unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(command.get_command(), "dispatch focuswindow class:nope");
+        assert_eq!(mock.received().len(), 3);
+    }
+
+    #[test]
+    fn test_recipe_stop_on_first_error() {
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("dispatch focuswindow class:nope", "Window not found");
+
+        let recipe = recipe();
+        let report = mock
+            .connection()
+            .send_recipe_with_report_sync(&recipe, RecipeMode::StopOnFirstError);
+
+        let outcomes = report.outcomes();
+        assert!(outcomes[0].1.is_success());
+        assert!(matches!(outcomes[1].1, CommandOutcome::Failed(_)));
+        assert!(matches!(outcomes[2].1, CommandOutcome::Skipped));
+        assert_eq!(
+            mock.received(),
+            [
+                "dispatch setfloating activewindow",
+                "dispatch focuswindow class:nope"
+            ]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::io;
+
+use super::HyprlandData;
+
+/// A tuple of [`HyprlandData`] types that can be requested together, in a single round-trip.
+///
+/// Implemented for tuples of up to 8 elements.
+pub trait HyprlandDataBatch: Sized {
+    fn get_commands() -> Vec<&'static str>;
+
+    fn from_replies(replies: &[&str]) -> Result<Self, serde_json::Error>;
+}
+
+macro_rules! impl_data_batch {
+    ($($name:ident),+) => {
+        impl<$($name: HyprlandData),+> HyprlandDataBatch for ($($name,)+) {
+            fn get_commands() -> Vec<&'static str> {
+                vec![$($name::get_command()),+]
+            }
+
+            fn from_replies(replies: &[&str]) -> Result<Self, serde_json::Error> {
+                let mut replies = replies.iter();
+                Ok(($($name::from_reply(replies.next().copied().unwrap_or_default())?,)+))
+            }
+        }
+    };
+}
+
+impl_data_batch!(A);
+impl_data_batch!(A, B);
+impl_data_batch!(A, B, C);
+impl_data_batch!(A, B, C, D);
+impl_data_batch!(A, B, C, D, E);
+impl_data_batch!(A, B, C, D, E, F);
+impl_data_batch!(A, B, C, D, E, F, G);
+impl_data_batch!(A, B, C, D, E, F, G, H);
+
+pub(crate) fn get_batch_command<T: HyprlandDataBatch>() -> String {
+    get_batch_from_requests(T::get_commands())
+}
+
+/// Builds the batch that sends every request in `requests`, asking for JSON replies.
+pub(crate) fn get_batch_from_requests<I>(requests: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut full_command = String::from("/[[BATCH]]");
+    for request in requests {
+        full_command.push_str("j/");
+        full_command.push_str(request.as_ref());
+        full_command.push(';');
+    }
+
+    full_command
+}
+
+pub(crate) fn parse_batch_reply<T: HyprlandDataBatch>(resp: &str) -> Result<T, io::Error> {
+    let replies = split_batch_reply(resp, T::get_commands().len())?;
+    Ok(T::from_replies(&replies)?)
+}
+
+/// Splits the reply to a batch of `count` requests into the reply to every request.
+pub(crate) fn split_batch_reply(resp: &str, count: usize) -> Result<Vec<&str>, io::Error> {
+    let replies = resp.split("\n\n\n").collect::<Vec<&str>>();
+    if replies.len() != count {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Hyprland sent a different number of replies than requested.",
+        ));
+    }
+
+    Ok(replies)
+}
@@ -3,7 +3,6 @@ use std::collections::HashMap;
 use crate::arguments::{Argument, MonitorsDataArgument, WindowArgument};
 
 use super::{HyprlandData, HyprlandDataWithArgument};
-use hyprrust_macros::{HyprlandData, HyprlandDataWithArgument};
 use serde::{Deserialize, Serialize};
 use std::ops::Deref;
 
@@ -18,7 +17,7 @@ macro_rules! auto_deref {
     };
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Clone, Default, Debug)]
 pub struct Sides {
     pub left: i32,
     pub top: i32,
@@ -49,13 +48,13 @@ pub struct Version {
     pub flags: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct WorkspaceBrief {
     pub id: i64,
     pub name: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Monitor {
     pub id: i64,
@@ -91,7 +90,7 @@ pub struct Monitor {
 pub struct Monitors(Vec<Monitor>);
 auto_deref!(Monitors = Vec<Monitor>);
 
-#[derive(Serialize, Deserialize, Debug, HyprlandData)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug, HyprlandData)]
 #[command = "activeworkspace"]
 pub struct Workspace {
     pub id: i64,
@@ -133,16 +132,17 @@ pub struct WorkspaceRule {
 pub struct WorkspaceRules(Vec<WorkspaceRule>);
 auto_deref!(WorkspaceRules = Vec<WorkspaceRule>);
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum FullscreenState {
+    #[default]
     None = 0,
     Maximized = 1,
     Fullscreen = 2,
     Max = 3,
 }
 
-#[derive(Serialize, Deserialize, Debug, HyprlandData)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug, HyprlandData)]
 #[serde(rename_all = "camelCase")]
 #[command = "activewindow"]
 pub struct Window {
@@ -187,7 +187,7 @@ pub struct Mouse {
     pub default_speed: f32,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Keyboard {
     pub address: String,
@@ -204,6 +204,18 @@ pub struct Keyboard {
     pub main: bool,
 }
 
+impl Keyboard {
+    /// Returns the configured layouts, in the order used by `switchxkblayout`.
+    pub fn layouts(&self) -> Vec<&str> {
+        self.layout.split(',').map(str::trim).collect()
+    }
+
+    /// Returns the variant of every layout, empty for layouts without one.
+    pub fn variants(&self) -> Vec<&str> {
+        self.variant.split(',').map(str::trim).collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct TabletPad {
@@ -307,10 +319,11 @@ pub struct Layers {
     pub monitors: HashMap<String, Levels>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OptionValue {
-    Int(i32),
+    /// Colors are reported as unsigned 32-bit ARGB values, which don't fit in an `i32`.
+    Int(i64),
     Float(f32),
     #[serde(rename = "str")]
     String(String),
@@ -318,7 +331,6 @@ pub enum OptionValue {
     Vec2([f32; 2]),
 }
 
-// NOTE: splash not implemented
 #[derive(Serialize, Deserialize, Debug, HyprlandDataWithArgument)]
 pub struct GetOption {
     #[serde(rename = "option")]
@@ -328,6 +340,22 @@ pub struct GetOption {
     set: bool,
 }
 
+impl GetOption {
+    /// Returns the name of the option, ie. `general:border_size`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn value(&self) -> &OptionValue {
+        &self.value
+    }
+
+    /// Returns whether the option was set in the config, or is using its default value
+    pub fn is_set(&self) -> bool {
+        self.set
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, HyprlandData)]
 #[command = "cursorpos"]
 pub struct CursorPosition {
@@ -372,3 +400,143 @@ pub struct ConfigErrors {
 pub struct Layouts {
     layouts: Vec<String>,
 }
+
+/// Hyprland sends the splash as plain text, even when JSON is requested.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct Splash(pub(crate) String);
+auto_deref!(Splash = String);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Plugin {
+    pub name: String,
+    pub author: String,
+    pub handle: String,
+    pub version: String,
+    pub description: String,
+}
+
+/// The loaded plugins. Load and unload them with [`load_plugin`] and [`unload_plugin`].
+///
+/// Dispatchers added by a plugin can be sent with commands made using [`make_command`], and its
+/// JSON requests can be fetched by deriving [`HyprlandData`](trait@HyprlandData):
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use hyprrust::commands::prelude::*;
+/// use hyprrust::data::{HyprlandData, Plugins};
+/// use hyprrust::HyprlandConnection;
+/// use serde::Deserialize;
+///
+/// make_command!(expo_toggle, "dispatch hyprexpo:expo toggle");
+///
+/// #[derive(Deserialize, HyprlandData)]
+/// #[command = "myplugin:status"]
+/// struct MyPluginStatus {
+///     enabled: bool,
+/// }
+///
+/// let conn = HyprlandConnection::current()?;
+/// conn.send_command(&unload_plugin("/path/to/myplugin.so".to_string())).await?;
+/// conn.send_command(&load_plugin("/path/to/myplugin.so".to_string())).await?;
+///
+/// let plugins = conn.get::<Plugins>().await?;
+/// assert!(plugins.iter().any(|plugin| plugin.name == "myplugin"));
+/// conn.send_command(&expo_toggle()).await?;
+/// println!("{}", conn.get::<MyPluginStatus>().await?.enabled);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`load_plugin`]: crate::commands::load_plugin
+/// [`unload_plugin`]: crate::commands::unload_plugin
+/// [`make_command`]: crate::commands::make_command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Plugins(pub(crate) Vec<Plugin>);
+auto_deref!(Plugins = Vec<Plugin>);
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GlobalShortcut {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, HyprlandData)]
+pub struct GlobalShortcuts(Vec<GlobalShortcut>);
+auto_deref!(GlobalShortcuts = Vec<GlobalShortcut>);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Instance {
+    pub instance: String,
+    pub time: u64,
+    pub pid: i32,
+    pub wl_socket: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, HyprlandData)]
+pub struct Instances(Vec<Instance>);
+auto_deref!(Instances = Vec<Instance>);
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RollingLog {
+    pub log: String,
+}
+
+/// Hyprland only sends the system info as plain text.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct SystemInfo(pub(crate) String);
+auto_deref!(SystemInfo = String);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, HyprlandData)]
+pub struct Locked {
+    pub locked: bool,
+}
+
+/// The name of the current submap, `default` if no submap is active.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct Submap(pub(crate) String);
+auto_deref!(Submap = String);
+
+/// Serialized as the number Hyprland uses for the type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionType {
+    Bool,
+    Int,
+    Float,
+    StringShort,
+    StringLong,
+    Color,
+    Choice,
+    Gradient,
+    Vector,
+    Other(u8),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OptionDescription {
+    #[serde(rename = "value")]
+    pub name: String,
+    pub description: String,
+    #[serde(rename = "type")]
+    pub option_type: OptionType,
+    pub flags: u32,
+    /// Depends on the type of the option, usually contains the current and default value, and
+    /// the allowed range.
+    pub data: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, HyprlandData)]
+pub struct Descriptions(Vec<OptionDescription>);
+auto_deref!(Descriptions = Vec<OptionDescription>);
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LayerRule {
+    pub rule: String,
+    pub namespace: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, HyprlandData)]
+pub struct LayerRules(Vec<LayerRule>);
+auto_deref!(LayerRules = Vec<LayerRule>);
@@ -1,8 +1,11 @@
 use crate::arguments::Argument;
 
-use super::data_models::{FullscreenState, Layer, Levels, Sides};
+use super::data_models::{
+    FullscreenState, Layer, Levels, OptionType, Plugins, RollingLog, Sides, Splash, Submap,
+    SystemInfo,
+};
 use serde::de::{DeserializeOwned, Error, Visitor};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
 impl<'de> Deserialize<'de> for FullscreenState {
@@ -22,14 +25,133 @@ impl<'de> Deserialize<'de> for FullscreenState {
     }
 }
 
+impl<'de> Deserialize<'de> for OptionType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use OptionType::*;
+
+        Ok(match <u8 as Deserialize>::deserialize(deserializer)? {
+            0 => Bool,
+            1 => Int,
+            2 => Float,
+            3 => StringShort,
+            4 => StringLong,
+            5 => Color,
+            6 => Choice,
+            7 => Gradient,
+            8 => Vector,
+            other => Other(other),
+        })
+    }
+}
+
+impl Serialize for OptionType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use OptionType::*;
+
+        serializer.serialize_u8(match self {
+            Bool => 0,
+            Int => 1,
+            Float => 2,
+            StringShort => 3,
+            StringLong => 4,
+            Color => 5,
+            Choice => 6,
+            Gradient => 7,
+            Vector => 8,
+            Other(other) => *other,
+        })
+    }
+}
+
 pub trait HyprlandData: DeserializeOwned {
     fn get_command() -> &'static str;
+
+    /// Parses the reply sent by Hyprland. Most replies are JSON, the types for which Hyprland
+    /// sends something else override this.
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(reply)
+    }
 }
 
 pub trait HyprlandDataWithArgument: DeserializeOwned {
     type Argument: Argument;
 
     fn get_command(arg: Self::Argument) -> String;
+
+    /// See [`HyprlandData::from_reply`].
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(reply)
+    }
+}
+
+impl HyprlandData for Splash {
+    fn get_command() -> &'static str {
+        "splash"
+    }
+
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        Ok(Splash(reply.trim_end().to_string()))
+    }
+}
+
+impl HyprlandData for SystemInfo {
+    fn get_command() -> &'static str {
+        "systeminfo"
+    }
+
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        Ok(SystemInfo(reply.trim_end().to_string()))
+    }
+}
+
+impl HyprlandData for Submap {
+    fn get_command() -> &'static str {
+        "submap"
+    }
+
+    /// Hyprland sends the name wrapped in braces (ie. `{"default"}`), which is not valid JSON.
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        let reply = reply.trim();
+        match reply.strip_prefix('{').and_then(|r| r.strip_suffix('}')) {
+            Some(name) => Ok(Submap(serde_json::from_str(name.trim())?)),
+            None => Ok(Submap(reply.to_string())),
+        }
+    }
+}
+
+impl HyprlandData for RollingLog {
+    fn get_command() -> &'static str {
+        "rollinglog"
+    }
+
+    /// Hyprland sends the log as `["log": "..."]`, which is not valid JSON.
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        let reply = reply.trim();
+        match reply.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            Some(inner) => serde_json::from_str(&format!("{{{}}}", inner)),
+            None => serde_json::from_str(reply),
+        }
+    }
+}
+
+impl HyprlandData for Plugins {
+    fn get_command() -> &'static str {
+        "plugin list"
+    }
+
+    /// Older versions of Hyprland send `no plugins loaded` as text, even when JSON is requested.
+    fn from_reply(reply: &str) -> Result<Self, serde_json::Error> {
+        match reply.trim() {
+            "no plugins loaded" => Ok(Plugins(Vec::new())),
+            reply => serde_json::from_str(reply),
+        }
+    }
 }
 
 struct SidesVisitor;
@@ -1,10 +1,18 @@
 mod data_models;
 pub use data_models::*;
+mod batch;
 mod deserializing;
 
 use crate::connection::HyprlandConnection;
+pub use batch::HyprlandDataBatch;
+#[cfg(any(feature = "async", feature = "sync"))]
+use batch::{get_batch_command, parse_batch_reply};
+#[cfg(any(feature = "async", feature = "sync"))]
+pub(crate) use batch::{get_batch_from_requests, split_batch_reply};
 pub use deserializing::{HyprlandData, HyprlandDataWithArgument};
+pub use hyprrust_macros::{HyprlandData, HyprlandDataWithArgument};
 
+#[cfg(any(feature = "async", feature = "sync"))]
 use std::io;
 
 impl HyprlandConnection {
@@ -21,7 +29,7 @@ impl HyprlandConnection {
         let command = format!("-j/{}", T::get_command());
         let resp = self.send_raw_message(command.as_str()).await?;
 
-        Ok(serde_json::from_str(resp.as_str())?)
+        Ok(T::from_reply(resp.as_str())?)
     }
 
     /// Returns the data T requested from Hyprland also passing an argument
@@ -37,7 +45,36 @@ impl HyprlandConnection {
         let command = format!("-j/{}", T::get_command(arg));
         let resp = self.send_raw_message(command.as_str()).await?;
 
-        Ok(serde_json::from_str(resp.as_str())?)
+        Ok(T::from_reply(resp.as_str())?)
+    }
+
+    /// Returns all the data types in the tuple T using a single request to Hyprland. This is
+    /// faster than calling [`get`] for each of them.
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), std::io::Error> {
+    /// use hyprrust::data::*;
+    /// use hyprrust::HyprlandConnection;
+    ///
+    /// let conn = HyprlandConnection::current().unwrap();
+    /// let (monitors, workspaces, cursor) = conn
+    ///     .get_batch::<(Monitors, Workspaces, CursorPosition)>()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`get`]: #method.get
+    #[cfg(feature = "async")]
+    pub async fn get_batch<T>(&self) -> Result<T, io::Error>
+    where
+        T: HyprlandDataBatch,
+    {
+        let resp = self
+            .send_raw_message(get_batch_command::<T>().as_str())
+            .await?;
+
+        parse_batch_reply(resp.as_str())
     }
 
     /// The same behaviour as [`get`], but without async.
@@ -52,7 +89,20 @@ impl HyprlandConnection {
         let command = format!("-j/{}", T::get_command());
         let resp = self.send_raw_message_sync(command.as_str())?;
 
-        Ok(serde_json::from_str(resp.as_str())?)
+        Ok(T::from_reply(resp.as_str())?)
+    }
+
+    /// The blocking counterpart of [`get_batch`].
+    ///
+    /// [`get_batch`]: #method.get_batch
+    #[cfg(feature = "sync")]
+    pub fn get_batch_sync<T>(&self) -> Result<T, io::Error>
+    where
+        T: HyprlandDataBatch,
+    {
+        let resp = self.send_raw_message_sync(get_batch_command::<T>().as_str())?;
+
+        parse_batch_reply(resp.as_str())
     }
 
     /// The same behaviour as get_with_argument, but without async.
@@ -67,7 +117,7 @@ impl HyprlandConnection {
         let command = format!("-j/{}", T::get_command(arg));
         let resp = self.send_raw_message_sync(command.as_str())?;
 
-        Ok(serde_json::from_str(resp.as_str())?)
+        Ok(T::from_reply(resp.as_str())?)
     }
 }
 
@@ -111,4 +161,101 @@ mod data_tests {
         assert!(conn.get_sync::<Layouts>().is_ok());
         assert!(conn.get_sync::<Workspace>().is_ok());
     }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_text_and_extra_data_models() {
+        use super::data_models::*;
+        use super::HyprlandData;
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("splash", "Hello from the mock!\n");
+        mock.reply("systeminfo", "Hyprland 0.50.0\nGPU: none\n\n");
+        mock.reply("submap", "{\"resize\"}\n");
+        mock.reply("rollinglog", "[\n\"log\":\"line 1\\nline 2\"]");
+        mock.reply("locked", r#"{"locked": false}"#);
+        mock.reply(
+            "instances",
+            r#"[{"instance": "abc", "time": 1, "pid": 2, "wl_socket": "wayland-1"}]"#,
+        );
+        mock.reply(
+            "globalshortcuts",
+            r#"[{"name": "app:toggle", "description": "Toggle"}]"#,
+        );
+        mock.reply(
+            "descriptions",
+            r#"[{"value": "general:border_size", "description": "size of the border",
+                 "type": 1, "flags": 0, "data": {"default": 1, "min": 0, "max": 20}}]"#,
+        );
+        mock.reply("layerrules", r#"[{"rule": "blur", "namespace": "waybar"}]"#);
+        mock.reply(
+            "plugin list",
+            r#"[{"name": "hyprexpo", "author": "Vaxry", "handle": "7f3a", "version": "1.0",
+                 "description": "Workspace overview"}]"#,
+        );
+
+        let conn = mock.connection();
+        assert_eq!(
+            conn.get_sync::<Splash>().unwrap().as_str(),
+            "Hello from the mock!"
+        );
+        assert_eq!(
+            conn.get_sync::<SystemInfo>().unwrap().as_str(),
+            "Hyprland 0.50.0\nGPU: none"
+        );
+        assert_eq!(conn.get_sync::<Submap>().unwrap().as_str(), "resize");
+        assert_eq!(conn.get_sync::<RollingLog>().unwrap().log, "line 1\nline 2");
+        assert!(!conn.get_sync::<Locked>().unwrap().locked);
+        assert_eq!(
+            conn.get_sync::<Instances>().unwrap()[0].wl_socket,
+            "wayland-1"
+        );
+        assert_eq!(
+            conn.get_sync::<GlobalShortcuts>().unwrap()[0].name,
+            "app:toggle"
+        );
+        let descriptions = conn.get_sync::<Descriptions>().unwrap();
+        assert_eq!(descriptions[0].option_type, OptionType::Int);
+        let round_trip: Descriptions =
+            serde_json::from_str(&serde_json::to_string(&descriptions).unwrap()).unwrap();
+        assert_eq!(round_trip[0].option_type, OptionType::Int);
+        assert_eq!(serde_json::to_string(&OptionType::Other(42)).unwrap(), "42");
+        assert_eq!(
+            conn.get_sync::<LayerRules>().unwrap()[0].namespace,
+            "waybar"
+        );
+        assert_eq!(conn.get_sync::<Plugins>().unwrap()[0].name, "hyprexpo");
+        assert!(Plugins::from_reply("no plugins loaded\n")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_batch_data() {
+        use super::data_models::*;
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("cursorpos", r#"{"x": 5, "y": 6}"#);
+        mock.reply("locked", r#"{"locked": true}"#);
+        mock.reply("workspaces", "[]");
+
+        let conn = mock.connection();
+        let (cursor, locked, workspaces) = conn
+            .get_batch_sync::<(CursorPosition, Locked, Workspaces)>()
+            .unwrap();
+        assert_eq!((cursor.x, cursor.y), (5, 6));
+        assert!(locked.locked);
+        assert!(workspaces.is_empty());
+        assert_eq!(mock.received(), ["cursorpos", "locked", "workspaces"]);
+    }
+
+    #[test]
+    fn test_split_batch_reply() {
+        assert_eq!(super::split_batch_reply("ok\n\n\n", 2).unwrap(), ["ok", ""]);
+        assert!(super::split_batch_reply("ok\n\n\n", 1).is_err());
+        assert!(super::split_batch_reply("ok\n\n\nok", 3).is_err());
+    }
 }
@@ -1,3 +1,4 @@
+#[cfg(any(feature = "async", feature = "sync"))]
 use std::io;
 #[cfg(feature = "sync")]
 use std::io::{Read, Write};
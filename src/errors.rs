@@ -12,6 +12,11 @@ impl HyprlandError {
     pub(crate) fn new(message: String) -> Self {
         HyprlandError { message }
     }
+
+    /// Returns the text sent by Hyprland
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 impl fmt::Display for HyprlandError {
@@ -22,17 +27,73 @@ impl fmt::Display for HyprlandError {
 
 impl Error for HyprlandError {}
 
+/// Returned when a command fails. Errors reported by Hyprland are classified using the reply
+/// text, every variant keeps the original reply. Replies that don't match any known failure end
+/// up in [`CommandError::HyprlandError`].
 #[derive(Debug)]
 pub enum CommandError {
     HyprlandError(HyprlandError),
+    UnknownRequest(HyprlandError),
+    UnknownDispatcher(HyprlandError),
+    InvalidArgument(HyprlandError),
+    NoSuchWindow(HyprlandError),
+    NoSuchMonitor(HyprlandError),
+    NoSuchWorkspace(HyprlandError),
+    /// A `keyword` command set a config option that doesn't exist
+    ConfigKeywordRejected(HyprlandError),
     IOError(io::Error),
 }
 
+impl CommandError {
+    /// Classifies `reply`, the error Hyprland sent back after receiving `command`. Only the exact
+    /// replies Hyprland sends are recognized, anything else is a [`CommandError::HyprlandError`].
+    pub(crate) fn from_reply(command: &str, reply: String) -> Self {
+        let variant: fn(HyprlandError) -> Self = match reply.trim() {
+            "unknown request" => Self::UnknownRequest,
+            "Invalid dispatcher" => Self::UnknownDispatcher,
+            // Sent when a bind uses a dispatcher that doesn't exist
+            r if r.starts_with("Invalid dispatcher, requested \"")
+                && r.ends_with("\" does not exist") =>
+            {
+                Self::UnknownDispatcher
+            }
+            "No such window found" | "Window not found" => Self::NoSuchWindow,
+            "No such monitor" | "Invalid monitor" => Self::NoSuchMonitor,
+            "Invalid workspace" => Self::NoSuchWorkspace,
+            "Invalid arg" | "Invalid argument" => Self::InvalidArgument,
+            r if command.starts_with("keyword ")
+                && r.starts_with("config option <")
+                && r.ends_with("> does not exist.") =>
+            {
+                Self::ConfigKeywordRejected
+            }
+            _ => Self::HyprlandError,
+        };
+
+        variant(HyprlandError::new(reply))
+    }
+
+    /// Returns the error sent by Hyprland, or `None` for IO errors.
+    pub fn hyprland_error(&self) -> Option<&HyprlandError> {
+        match self {
+            Self::HyprlandError(e)
+            | Self::UnknownRequest(e)
+            | Self::UnknownDispatcher(e)
+            | Self::InvalidArgument(e)
+            | Self::NoSuchWindow(e)
+            | Self::NoSuchMonitor(e)
+            | Self::NoSuchWorkspace(e)
+            | Self::ConfigKeywordRejected(e) => Some(e),
+            Self::IOError(_) => None,
+        }
+    }
+}
+
 impl fmt::Display for CommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::HyprlandError(e) => write!(f, "{}", e),
             Self::IOError(e) => write!(f, "{}", e),
+            _ => write!(f, "{}", self.hyprland_error().unwrap()),
         }
     }
 }
@@ -40,8 +101,320 @@ impl fmt::Display for CommandError {
 impl Error for CommandError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            Self::HyprlandError(e) => Some(e),
             Self::IOError(e) => Some(e),
+            _ => self.hyprland_error().map(|e| e as &(dyn Error + 'static)),
+        }
+    }
+}
+
+/// Returned when launching a program and waiting for its window fails
+#[derive(Debug)]
+pub enum LaunchError {
+    /// The command that launches the program failed
+    CommandError(CommandError),
+    /// No matching window opened before the timeout
+    Timeout,
+    IOError(io::Error),
+}
+
+impl From<CommandError> for LaunchError {
+    fn from(value: CommandError) -> Self {
+        Self::CommandError(value)
+    }
+}
+
+impl From<io::Error> for LaunchError {
+    fn from(value: io::Error) -> Self {
+        Self::IOError(value)
+    }
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CommandError(e) => write!(f, "{}", e),
+            Self::Timeout => write!(f, "no matching window opened before the timeout"),
+            Self::IOError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for LaunchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CommandError(e) => Some(e),
+            Self::Timeout => None,
+            Self::IOError(e) => Some(e),
+        }
+    }
+}
+
+/// Returned when adding a keybind fails
+#[derive(Debug)]
+pub enum KeybindError {
+    /// The keys are already bound, for example in the config. Binding them again would make
+    /// removing the new bind also remove the existing ones.
+    AlreadyBound(String),
+    CommandError(CommandError),
+    IOError(io::Error),
+}
+
+impl From<CommandError> for KeybindError {
+    fn from(value: CommandError) -> Self {
+        Self::CommandError(value)
+    }
+}
+
+impl From<io::Error> for KeybindError {
+    fn from(value: io::Error) -> Self {
+        Self::IOError(value)
+    }
+}
+
+impl fmt::Display for KeybindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyBound(keys) => write!(f, "{} is already bound", keys),
+            Self::CommandError(e) => write!(f, "{}", e),
+            Self::IOError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for KeybindError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AlreadyBound(_) => None,
+            Self::CommandError(e) => Some(e),
+            Self::IOError(e) => Some(e),
+        }
+    }
+}
+
+/// Returned when a string can't be parsed into a command argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentParseError {
+    value: String,
+    expected: &'static str,
+}
+
+impl ArgumentParseError {
+    pub(crate) fn new(value: &str, expected: &'static str) -> Self {
+        ArgumentParseError {
+            value: value.to_string(),
+            expected,
+        }
+    }
+
+    /// Returns the string that could not be parsed
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for ArgumentParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" is not a valid {}", self.value, self.expected)
+    }
+}
+
+impl Error for ArgumentParseError {}
+
+/// Returned when a string can't be parsed into a [`Dispatcher`].
+///
+/// [`Dispatcher`]: crate::commands::Dispatcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandParseError {
+    /// The string is not a dispatch command, ie. it doesn't start with `dispatch`
+    NotADispatch(String),
+    UnknownDispatcher(String),
+    MissingArgument {
+        dispatcher: String,
+    },
+    TooManyArguments {
+        dispatcher: String,
+    },
+    InvalidArgument {
+        dispatcher: String,
+        error: ArgumentParseError,
+    },
+}
+
+impl fmt::Display for CommandParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotADispatch(command) => write!(f, "\"{}\" is not a dispatch command", command),
+            Self::UnknownDispatcher(dispatcher) => write!(f, "unknown dispatcher {}", dispatcher),
+            Self::MissingArgument { dispatcher } => {
+                write!(f, "missing argument for dispatcher {}", dispatcher)
+            }
+            Self::TooManyArguments { dispatcher } => {
+                write!(f, "too many arguments for dispatcher {}", dispatcher)
+            }
+            Self::InvalidArgument { dispatcher, error } => {
+                write!(
+                    f,
+                    "invalid argument for dispatcher {}: {}",
+                    dispatcher, error
+                )
+            }
+        }
+    }
+}
+
+impl Error for CommandParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidArgument { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Returned when reading or setting a config option fails
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Hyprland doesn't have an option with this name
+    UnknownOption(String),
+    /// The option holds a different type of value than the one requested
+    TypeMismatch {
+        option: String,
+    },
+    /// The value can't be used for the option, ie. it has the wrong type or is out of range
+    InvalidValue {
+        option: String,
+        value: String,
+    },
+    CommandError(CommandError),
+    IOError(io::Error),
+}
+
+impl From<CommandError> for ConfigError {
+    fn from(value: CommandError) -> Self {
+        Self::CommandError(value)
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(value: io::Error) -> Self {
+        Self::IOError(value)
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOption(option) => write!(f, "unknown config option {}", option),
+            Self::TypeMismatch { option } => {
+                write!(f, "config option {} holds a different type", option)
+            }
+            Self::InvalidValue { option, value } => {
+                write!(f, "\"{}\" is not a valid value for {}", value, option)
+            }
+            Self::CommandError(e) => write!(f, "{}", e),
+            Self::IOError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CommandError(e) => Some(e),
+            Self::IOError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod errors_tests {
+    use super::CommandError;
+
+    fn classify(command: &str, reply: &str) -> CommandError {
+        CommandError::from_reply(command, reply.to_string())
+    }
+
+    #[test]
+    fn test_error_classification() {
+        assert!(matches!(
+            classify("foo", "unknown request\n"),
+            CommandError::UnknownRequest(_)
+        ));
+        assert!(matches!(
+            classify("dispatch foo", "Invalid dispatcher"),
+            CommandError::UnknownDispatcher(_)
+        ));
+        assert!(matches!(
+            classify("dispatch focuswindow class:x", "No such window found"),
+            CommandError::NoSuchWindow(_)
+        ));
+        assert!(matches!(
+            classify("dispatch focuswindow class:x", "Window not found"),
+            CommandError::NoSuchWindow(_)
+        ));
+        assert!(matches!(
+            classify("dispatch focusmonitor DP-9", "No such monitor"),
+            CommandError::NoSuchMonitor(_)
+        ));
+        assert!(matches!(
+            classify("dispatch focusmonitor DP-9", "Invalid monitor"),
+            CommandError::NoSuchMonitor(_)
+        ));
+        assert!(matches!(
+            classify("dispatch workspace name:", "Invalid workspace"),
+            CommandError::NoSuchWorkspace(_)
+        ));
+        assert!(matches!(
+            classify("dispatch splitratio x", "Invalid arg"),
+            CommandError::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            classify("dispatch movecursor x", "Invalid argument"),
+            CommandError::InvalidArgument(_)
+        ));
+
+        let error = classify("dispatch exit", "something odd");
+        assert!(matches!(error, CommandError::HyprlandError(_)));
+        assert_eq!(error.hyprland_error().unwrap().message(), "something odd");
+    }
+
+    #[test]
+    fn test_keyword_error_classification() {
+        assert!(matches!(
+            classify(
+                "keyword bind SUPER,Q,notadispatcher",
+                "Invalid dispatcher, requested \"notadispatcher\" does not exist"
+            ),
+            CommandError::UnknownDispatcher(_)
+        ));
+        assert!(matches!(
+            classify(
+                "keyword decoration:roundin 4",
+                "config option <decoration:roundin> does not exist."
+            ),
+            CommandError::ConfigKeywordRejected(_)
+        ));
+        assert!(matches!(
+            classify(
+                "dispatch exec config option <x> does not exist.",
+                "config option <x> does not exist."
+            ),
+            CommandError::HyprlandError(_)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_replies_are_not_classified() {
+        // Mentions a window, a workspace and something invalid, but isn't any of those errors
+        for reply in [
+            "Invalid rulev2 found: workspace 3 silent, window not found",
+            "invalid field floatt: missing a value",
+        ] {
+            assert!(matches!(
+                classify("keyword windowrulev2 workspace 3 silent,class:kitty", reply),
+                CommandError::HyprlandError(_)
+            ));
         }
     }
 }
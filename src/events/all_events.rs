@@ -149,4 +149,11 @@ pub enum HyprlandEvent {
     Custom {
         data: String,
     },
+    /// Not sent by Hyprland. Sent by the event listener when the event socket gets closed.
+    Disconnected,
+    /// Not sent by Hyprland. Sent by the event listener after it managed to reconnect to
+    /// `instance`.
+    Reconnected {
+        instance: String,
+    },
 }
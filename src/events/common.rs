@@ -100,7 +100,7 @@ pub(crate) fn parse_event(msg: &str, filter: &EventFilter) -> Result<HyprlandEve
         "moveworkspacev2" => HyprlandEvent::MoveWorkspaceV2 {
             id: parse_int(argv[0])?,
             name: argv[1].to_owned(),
-            mon_name: argv[1].to_owned(),
+            mon_name: argv[2].to_owned(),
         },
         "renameworkspace" => HyprlandEvent::RenameWorkspace {
             id: parse_int(argv[0])?,
@@ -270,3 +270,22 @@ where
         filter
     }
 }
+
+#[cfg(test)]
+mod common_tests {
+    use super::{parse_event, EventFilter};
+    use crate::events::HyprlandEvent;
+
+    #[test]
+    fn test_parse_move_workspace_v2() {
+        let event = parse_event(
+            "moveworkspacev2>>3,code,DP-2\n",
+            &EventFilter::new_include_all(),
+        );
+        assert!(matches!(
+            event,
+            Ok(HyprlandEvent::MoveWorkspaceV2 { id: 3, name, mon_name })
+                if name == "code" && mon_name == "DP-2"
+        ));
+    }
+}
@@ -1,25 +1,197 @@
 use std::io;
-use std::str;
+use std::time::Duration;
 
 use super::common::*;
+use super::HyprlandEventType;
 use crate::connection::HyprlandConnection;
 
-use log::warn;
-use tokio::io::Interest;
+use log::{info, warn};
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::net::UnixStream;
 use tokio::sync::broadcast;
 use tokio::time;
 
 pub use super::HyprlandEvent;
 
+/// Decides what the event listener does when the event socket gets closed, for example when
+/// Hyprland restarts.
+///
+/// Every time the socket closes, a [`HyprlandEvent::Disconnected`] is sent. The listener then
+/// tries to reconnect, waiting `initial_delay` before the first attempt and doubling the delay
+/// after every failed attempt, up to `max_delay`. When it succeeds a
+/// [`HyprlandEvent::Reconnected`] is sent. When it runs out of retries the broadcast channel
+/// closes.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    max_retries: Option<u32>,
+    initial_delay: Duration,
+    max_delay: Duration,
+    resolve_instance: bool,
+}
+
+impl ReconnectPolicy {
+    /// Creates a new policy. `max_retries == None` means the listener never gives up.
+    pub fn new(max_retries: Option<u32>, initial_delay: Duration, max_delay: Duration) -> Self {
+        ReconnectPolicy {
+            max_retries,
+            initial_delay,
+            max_delay,
+            resolve_instance: false,
+        }
+    }
+
+    /// Creates a policy that never reconnects. The channel closes as soon as the socket does.
+    pub fn never() -> Self {
+        Self::new(Some(0), Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Sets whether the listener should look for the newest Hyprland instance before every
+    /// attempt. A restarted Hyprland gets a new instance signature, so without this the listener
+    /// only reconnects if the old socket comes back.
+    pub fn set_resolve_instance(&mut self, resolve_instance: bool) {
+        self.resolve_instance = resolve_instance;
+    }
+
+    /// Builder variant of [`Self::set_resolve_instance`].
+    pub fn with_resolve_instance(mut self, resolve_instance: bool) -> Self {
+        self.set_resolve_instance(resolve_instance);
+        self
+    }
+}
+
+impl Default for ReconnectPolicy {
+    /// Retries forever, starting with a delay of 100ms and backing off up to 5s, and follows
+    /// Hyprland to its newest instance.
+    fn default() -> Self {
+        Self::new(None, Duration::from_millis(100), Duration::from_secs(5))
+            .with_resolve_instance(true)
+    }
+}
+
+/// Sends `event` if it passes the filter. Returns false if nobody is listening anymore.
+fn send_event(
+    tx: &broadcast::Sender<HyprlandEvent>,
+    filter: &EventFilter,
+    ev_type: HyprlandEventType,
+    event: HyprlandEvent,
+) -> bool {
+    if !filter.includes(ev_type.get_name()) {
+        return tx.receiver_count() > 0;
+    }
+
+    tx.send(event).is_ok()
+}
+
+async fn reconnect(
+    conn: &mut HyprlandConnection,
+    policy: &ReconnectPolicy,
+    tx: &broadcast::Sender<HyprlandEvent>,
+) -> Option<BufReader<UnixStream>> {
+    let mut delay = policy.initial_delay;
+    let mut attempt = 0;
+
+    loop {
+        if policy
+            .max_retries
+            .is_some_and(|max_retries| attempt >= max_retries)
+        {
+            return None;
+        }
+
+        attempt += 1;
+        time::sleep(delay).await;
+
+        if tx.receiver_count() == 0 {
+            return None;
+        }
+
+        if policy.resolve_instance {
+            if let Some(instance) = conn.get_newest_instance() {
+                *conn = conn.with_instance(instance);
+            }
+        }
+
+        if let Ok(path) = conn.get_event_socket_path() {
+            if let Ok(socket) = UnixStream::connect(path).await {
+                return Some(BufReader::new(socket));
+            }
+        }
+
+        info!(attempt; "Could not reconnect to the event socket");
+        delay = (delay * 2).min(policy.max_delay);
+    }
+}
+
+async fn listen(
+    mut conn: HyprlandConnection,
+    mut reader: BufReader<UnixStream>,
+    filter: EventFilter,
+    policy: ReconnectPolicy,
+    tx: broadcast::Sender<HyprlandEvent>,
+) {
+    let mut buf = String::with_capacity(1024);
+    loop {
+        buf.clear();
+        match reader.read_line(&mut buf).await {
+            Ok(len) if len > 0 => {
+                if let Ok(event) = parse_event(&buf, &filter) {
+                    if tx.send(event).is_err() {
+                        return;
+                    } else if tx.len() >= 64 {
+                        warn!("Event channel is full");
+                    }
+                }
+            }
+            _ => {
+                if !send_event(
+                    &tx,
+                    &filter,
+                    HyprlandEventType::Disconnected,
+                    HyprlandEvent::Disconnected,
+                ) {
+                    return;
+                }
+
+                match reconnect(&mut conn, &policy, &tx).await {
+                    Some(new_reader) => reader = new_reader,
+                    None => return,
+                }
+
+                let event = HyprlandEvent::Reconnected {
+                    instance: conn.instance().to_string(),
+                };
+                if !send_event(&tx, &filter, HyprlandEventType::Reconnected, event) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
 impl HyprlandConnection {
     /// Spawns a task that listens to Hyprland events and sends them through an async channel. If a
     /// connection already exists, it gets restarted with the new filter provided.
     ///
+    /// The channel closes as soon as the event socket does, right after sending
+    /// [`HyprlandEvent::Disconnected`]. Use [`listen_to_events_with_policy`] to reconnect instead.
+    ///
     /// [`stop_listening`]: #method.stop_listening
+    /// [`listen_to_events_with_policy`]: #method.listen_to_events_with_policy
     pub async fn listen_to_events(
         &mut self,
         filter: EventFilter,
+    ) -> Result<broadcast::Receiver<HyprlandEvent>, io::Error> {
+        self.listen_to_events_with_policy(filter, ReconnectPolicy::never())
+            .await
+    }
+
+    /// Works the same as [`listen_to_events`], but uses `policy` when the event socket closes.
+    ///
+    /// [`listen_to_events`]: #method.listen_to_events
+    pub async fn listen_to_events_with_policy(
+        &mut self,
+        filter: EventFilter,
+        policy: ReconnectPolicy,
     ) -> Result<broadcast::Receiver<HyprlandEvent>, io::Error> {
         if self.event_handle.is_some() {
             self.stop_listening();
@@ -30,34 +202,9 @@ impl HyprlandConnection {
 
         let (tx, rx) = broadcast::channel(64);
 
-        let abort_handle = tokio::spawn(async move {
-            'main_loop: loop {
-                socket.ready(Interest::READABLE).await.unwrap();
-
-                let mut buf = [0; 1024];
-                match socket.try_read(&mut buf) {
-                    Ok(len) => {
-                        let str_buf = str::from_utf8(&buf[..len])
-                            .unwrap()
-                            .strip_suffix('\n')
-                            .unwrap();
-                        for line in str_buf.split('\n') {
-                            if let Ok(event) = parse_event(line, &filter) {
-                                if tx.send(event).is_err() {
-                                    break 'main_loop;
-                                } else if tx.len() >= 64 {
-                                    warn!("Event channel is full");
-                                }
-                            }
-                        }
-                    }
-                    Err(_) => {
-                        time::sleep(time::Duration::from_millis(100)).await;
-                    }
-                }
-            }
-        })
-        .abort_handle();
+        let conn = self.with_instance(self.instance().to_string());
+        let abort_handle =
+            tokio::spawn(listen(conn, BufReader::new(socket), filter, policy, tx)).abort_handle();
 
         self.event_handle = Some(abort_handle);
 
@@ -83,3 +230,87 @@ impl HyprlandConnection {
         }
     }
 }
+
+#[cfg(test)]
+mod events_conn_tests {
+    use std::time::Duration;
+
+    use super::ReconnectPolicy;
+    use crate::events::{EventFilter, HyprlandEvent};
+    use crate::testing::MockHyprland;
+
+    fn fast_policy(max_retries: Option<u32>) -> ReconnectPolicy {
+        ReconnectPolicy::new(
+            max_retries,
+            Duration::from_millis(5),
+            Duration::from_millis(20),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reconnect() {
+        let mock = MockHyprland::start().unwrap();
+        let mut conn = mock.connection();
+
+        let mut rx = conn
+            .listen_to_events_with_policy(EventFilter::new_include_all(), fast_policy(None))
+            .await
+            .unwrap();
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        mock.disconnect_event_listeners();
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            HyprlandEvent::Disconnected
+        ));
+        match rx.recv().await.unwrap() {
+            HyprlandEvent::Reconnected { instance } => assert_eq!(instance, mock.instance()),
+            ev => panic!("unexpected event {:?}", ev),
+        }
+
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+        mock.push_event("submap>>resize");
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            HyprlandEvent::Submap { name } if name == "resize"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_gives_up() {
+        let mock = MockHyprland::start().unwrap();
+        let mut conn = mock.connection();
+
+        let mut rx = conn
+            .listen_to_events_with_policy(EventFilter::new_include_all(), fast_policy(Some(2)))
+            .await
+            .unwrap();
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        drop(mock);
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            HyprlandEvent::Disconnected
+        ));
+        assert!(rx.recv().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_no_reconnect_by_default() {
+        let mock = MockHyprland::start().unwrap();
+        let mut conn = mock.connection();
+
+        let mut rx = conn
+            .listen_to_events(EventFilter::new_include_all())
+            .await
+            .unwrap();
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        mock.disconnect_event_listeners();
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            HyprlandEvent::Disconnected
+        ));
+        assert!(rx.recv().await.is_err());
+    }
+}
@@ -6,6 +6,8 @@ mod events_conn;
 
 pub use all_events::{HyprlandEvent, HyprlandEventType};
 pub use common::EventFilter;
+#[cfg(feature = "async")]
+pub use events_conn::ReconnectPolicy;
 
 #[cfg(feature = "async")]
 pub mod single_event;
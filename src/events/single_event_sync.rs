@@ -1,6 +1,7 @@
 use std::{
     io::{self, BufRead, BufReader},
     os::unix::net::UnixStream,
+    time::Duration,
 };
 
 use crate::HyprlandConnection;
@@ -12,22 +13,15 @@ use super::{common::parse_event, EventFilter, HyprlandEvent};
 pub struct DetachedEventConnection {
     socket: BufReader<UnixStream>,
     filter: EventFilter,
+    buf: String,
 }
 
 impl Iterator for DetachedEventConnection {
     type Item = Result<HyprlandEvent, io::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut buf = String::with_capacity(1024);
-        match self.socket.read_line(&mut buf) {
-            Ok(len) => {
-                let event = parse_event(&buf[..len], &self.filter);
-                if let Ok(event) = event {
-                    Some(Ok(event))
-                } else {
-                    None
-                }
-            }
+        match self.read_event()? {
+            Ok(event) => event.map(Ok),
             Err(e) => Some(Err(e)),
         }
     }
@@ -45,6 +39,90 @@ impl DetachedEventConnection {
         Ok(DetachedEventConnection {
             socket: BufReader::new(UnixStream::connect(path)?),
             filter,
+            buf: String::with_capacity(1024),
         })
     }
+
+    /// Sets how long the iterator waits for an event. When the timeout expires, it returns an
+    /// error of kind [`io::ErrorKind::WouldBlock`] or [`io::ErrorKind::TimedOut`] and can be used
+    /// again. `None` means it waits forever, which is the default.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), io::Error> {
+        self.socket.get_ref().set_read_timeout(timeout)
+    }
+
+    /// Reads one line. Events that are filtered out or malformed are returned as `Ok(None)`.
+    fn read_event(&mut self) -> Option<Result<Option<HyprlandEvent>, io::Error>> {
+        // A read that times out keeps the part of the line it got in `buf`
+        match self.socket.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                let event = parse_event(&self.buf, &self.filter).ok();
+                self.buf.clear();
+                Some(Ok(event))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Works like [`Iterator::next`], but skips the events that are filtered out or malformed
+    /// instead of stopping at them.
+    pub(crate) fn next_matching(&mut self) -> Option<Result<HyprlandEvent, io::Error>> {
+        loop {
+            match self.read_event()? {
+                Ok(Some(event)) => return Some(Ok(event)),
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    /// Returns an iterator over the events [`Self::next_matching`] returns.
+    pub(crate) fn matching(mut self) -> impl Iterator<Item = Result<HyprlandEvent, io::Error>> {
+        std::iter::from_fn(move || self.next_matching())
+    }
+}
+
+#[cfg(test)]
+mod single_event_sync_tests {
+    use std::time::Duration;
+
+    use super::DetachedEventConnection;
+    use crate::events::{EventFilter, HyprlandEvent, HyprlandEventType};
+    use crate::testing::MockHyprland;
+
+    #[test]
+    fn test_stops_at_filtered_events() {
+        let mock = MockHyprland::start().unwrap();
+        let mut filter = EventFilter::new_exclude_all();
+        filter.add_event(&HyprlandEventType::Submap);
+        let mut events =
+            DetachedEventConnection::from_connection(mock.connection(), filter).unwrap();
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        mock.push_event("submap>>resize");
+        mock.push_event("workspace>>2");
+        assert!(matches!(
+            events.next(),
+            Some(Ok(HyprlandEvent::Submap { name })) if name == "resize"
+        ));
+        assert!(events.next().is_none());
+    }
+
+    #[test]
+    fn test_matching_skips_filtered_and_malformed_events() {
+        let mock = MockHyprland::start().unwrap();
+        let mut filter = EventFilter::new_exclude_all();
+        filter.add_event(&HyprlandEventType::Submap);
+        let mut events =
+            DetachedEventConnection::from_connection(mock.connection(), filter).unwrap();
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        mock.push_event("workspace>>2");
+        mock.push_event("garbage");
+        mock.push_event("submap>>resize");
+        assert!(matches!(
+            events.next_matching(),
+            Some(Ok(HyprlandEvent::Submap { name })) if name == "resize"
+        ));
+    }
 }
@@ -0,0 +1,521 @@
+//! Keybinds added at runtime, that call Rust closures when pressed.
+//!
+//! A [`Keybind`] is bound with `keyword bind` to the `event` dispatcher, which makes Hyprland send
+//! a `custom` event with a token unique to the bind. [`Keybinds`] routes those events to the
+//! handler of the bind. Binds are removed when their [`KeybindHandle`] is dropped.
+//!
+//! Hyprland unbinds by keys, so removing a bind would also remove any other bind of the same
+//! keys. Binding keys that are already bound fails with [`KeybindError::AlreadyBound`].
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use hyprrust::arguments::{KeyArgument, ModArgument};
+//! use hyprrust::keybinds::{Keybind, Keybinds};
+//! use hyprrust::HyprlandConnection;
+//!
+//! let mut conn = HyprlandConnection::current()?;
+//! let keybinds = Keybinds::new();
+//!
+//! let _handle = keybinds
+//!     .bind(
+//!         &conn,
+//!         Keybind::new(&[ModArgument::Super, ModArgument::Shift], KeyArgument::Char('p')),
+//!         || println!("pressed"),
+//!     )
+//!     .await?;
+//!
+//! keybinds.run(&mut conn).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::io;
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::process;
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[cfg(any(feature = "async", feature = "sync"))]
+use serde::Deserialize;
+
+use crate::arguments::{Argument, KeyArgument, ModArgument};
+use crate::commands::{set_config_value, Command};
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::errors::{CommandError, KeybindError};
+use crate::events::HyprlandEvent;
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::events::{EventFilter, HyprlandEventType};
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::HyprlandConnection;
+
+#[cfg(any(feature = "async", feature = "sync"))]
+static BIND_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A key combination and the kind of bind it's registered as.
+#[derive(Debug, Clone)]
+pub struct Keybind {
+    modifiers: Vec<ModArgument>,
+    key: KeyArgument,
+    repeat: bool,
+    locked: bool,
+    release: bool,
+}
+
+impl Keybind {
+    pub fn new(modifiers: &[ModArgument], key: KeyArgument) -> Self {
+        Keybind {
+            modifiers: modifiers.to_vec(),
+            key,
+            repeat: false,
+            locked: false,
+            release: false,
+        }
+    }
+
+    /// Repeats while the keys are held down (`binde`).
+    pub fn with_repeat(mut self, repeat: bool) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Also works when an input inhibitor, ie. a lockscreen, is active (`bindl`).
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    /// Triggers when the keys are released instead of pressed (`bindr`).
+    pub fn with_release(mut self, release: bool) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Returns the modifiers and the key, as written in binds.
+    fn keys(&self) -> String {
+        let modifiers: Vec<String> = self
+            .modifiers
+            .iter()
+            .map(Argument::to_argument_string)
+            .collect();
+        format!("{},{}", modifiers.join(" "), self.key.to_argument_string())
+    }
+
+    /// Returns the `keyword bind` command that makes this bind send `dispatcher` with `argument`.
+    pub fn bind_command(&self, dispatcher: &str, argument: &str) -> Command {
+        let mut keyword = String::from("bind");
+        for (set, flag) in [(self.repeat, 'e'), (self.locked, 'l'), (self.release, 'r')] {
+            if set {
+                keyword.push(flag);
+            }
+        }
+
+        set_config_value(
+            keyword,
+            format!("{},{},{}", self.keys(), dispatcher, argument),
+        )
+    }
+
+    /// Returns the `keyword unbind` command that removes this bind. It removes every bind of these
+    /// keys, including the ones from the config.
+    pub fn unbind_command(&self) -> Command {
+        set_config_value("unbind".to_string(), self.keys())
+    }
+
+    /// Returns the modifiers as the bitmask Hyprland reports in `binds`.
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn modmask(&self) -> u32 {
+        self.modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                ModArgument::Shift => 1 << 0,
+                ModArgument::Caps => 1 << 1,
+                ModArgument::Ctrl => 1 << 2,
+                ModArgument::AltMod => 1 << 3,
+                ModArgument::Mod2 => 1 << 4,
+                ModArgument::Mod3 => 1 << 5,
+                ModArgument::Super => 1 << 6,
+                ModArgument::Mod5 => 1 << 7,
+            })
+            .fold(0, |mask, bit| mask | bit)
+    }
+
+    /// Returns whether one of `binds`, the reply of `j/binds`, uses the same keys.
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn is_bound_in(&self, binds: &str) -> Result<bool, io::Error> {
+        let binds: Vec<BoundKeys> = serde_json::from_str(binds)?;
+        Ok(binds.iter().any(|bind| {
+            bind.modmask == self.modmask()
+                && match self.key {
+                    KeyArgument::Char(c) => bind.key.eq_ignore_ascii_case(&c.to_string()),
+                    KeyArgument::Code(code) => bind.keycode == code,
+                    KeyArgument::Mouse(_) => bind.key == self.key.to_argument_string(),
+                }
+        }))
+    }
+
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn check_unbound(&self, binds: &str) -> Result<(), KeybindError> {
+        match self.is_bound_in(binds)? {
+            true => Err(KeybindError::AlreadyBound(self.keys())),
+            false => Ok(()),
+        }
+    }
+}
+
+/// The keys of a bind in the reply of `j/binds`. Binds by key code have an empty `key`.
+#[cfg(any(feature = "async", feature = "sync"))]
+#[derive(Deserialize)]
+struct BoundKeys {
+    modmask: u32,
+    key: String,
+    #[serde(default)]
+    keycode: u32,
+}
+
+type Handler = Arc<Mutex<dyn FnMut() + Send>>;
+
+/// Calls the handlers of binds when Hyprland sends the event of the bind.
+///
+/// Clones share the same handlers, so one clone can [run](Self::run) while others add binds.
+#[derive(Clone, Default)]
+pub struct Keybinds {
+    handlers: Arc<Mutex<HashMap<String, Handler>>>,
+}
+
+impl fmt::Debug for Keybinds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keybinds")
+            .field("binds", &self.handlers.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl Keybinds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` under a new token and returns the handle and the bind command.
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn register<F>(
+        &self,
+        conn: &HyprlandConnection,
+        bind: Keybind,
+        handler: F,
+    ) -> (KeybindHandle, Command)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let token = format!(
+            "hyprrust-bind-{}-{}",
+            process::id(),
+            BIND_COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        let command = bind.bind_command("event", &token);
+        self.handlers
+            .lock()
+            .unwrap()
+            .insert(token.clone(), Arc::new(Mutex::new(handler)));
+
+        let handle = KeybindHandle {
+            conn: conn.with_instance(conn.instance().to_string()),
+            bind,
+            token,
+            handlers: self.handlers.clone(),
+            bound: true,
+        };
+        (handle, command)
+    }
+
+    /// Binds `bind` and calls `handler` every time it's pressed, while [`Self::run`] is running.
+    /// Handlers are called from the task that runs, so they shouldn't block. Fails if the keys
+    /// are already bound.
+    #[cfg(feature = "async")]
+    pub async fn bind<F>(
+        &self,
+        conn: &HyprlandConnection,
+        bind: Keybind,
+        handler: F,
+    ) -> Result<KeybindHandle, KeybindError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        bind.check_unbound(&conn.send_raw_message("j/binds").await?)?;
+
+        let (mut handle, command) = self.register(conn, bind, handler);
+        if let Err(e) = conn.send_command(&command).await {
+            handle.bound = false;
+            return Err(e.into());
+        }
+        Ok(handle)
+    }
+
+    /// The blocking counterpart of [`Self::bind`].
+    #[cfg(feature = "sync")]
+    pub fn bind_sync<F>(
+        &self,
+        conn: &HyprlandConnection,
+        bind: Keybind,
+        handler: F,
+    ) -> Result<KeybindHandle, KeybindError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        bind.check_unbound(&conn.send_raw_message_sync("j/binds")?)?;
+
+        let (mut handle, command) = self.register(conn, bind, handler);
+        if let Err(e) = conn.send_command_sync(&command) {
+            handle.bound = false;
+            return Err(e.into());
+        }
+        Ok(handle)
+    }
+
+    /// Calls the handler of the bind that sent `event`. Returns whether the event was sent by one
+    /// of these binds.
+    pub fn handle_event(&self, event: &HyprlandEvent) -> bool {
+        let HyprlandEvent::Custom { data } = event else {
+            return false;
+        };
+
+        // The map isn't locked while the handler runs, so handlers can add or remove binds
+        let handler = self.handlers.lock().unwrap().get(data.trim()).cloned();
+        match handler {
+            Some(handler) => {
+                (*handler.lock().unwrap())();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Listens to events and calls the handlers of the binds that are pressed, until the event
+    /// channel closes.
+    #[cfg(feature = "async")]
+    pub async fn run(&self, conn: &mut HyprlandConnection) -> Result<(), io::Error> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let mut rx = conn.listen_to_events(custom_event_filter()).await?;
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    self.handle_event(&event);
+                }
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+
+    /// The blocking counterpart of [`Self::run`].
+    #[cfg(feature = "sync")]
+    pub fn run_sync(&self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        use crate::events::single_event_sync::DetachedEventConnection;
+
+        let events = DetachedEventConnection::from_connection(
+            conn.with_instance(conn.instance().to_string()),
+            custom_event_filter(),
+        )?;
+        for event in events.matching() {
+            self.handle_event(&event?);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+fn custom_event_filter() -> EventFilter {
+    let mut filter = EventFilter::new_exclude_all();
+    filter.add_event(&HyprlandEventType::Custom);
+    filter
+}
+
+/// A bind added by [`Keybinds`]. The bind is removed and its handler is dropped when the handle
+/// is dropped or [unbound](Self::unbind).
+#[cfg(any(feature = "async", feature = "sync"))]
+pub struct KeybindHandle {
+    conn: HyprlandConnection,
+    bind: Keybind,
+    token: String,
+    handlers: Arc<Mutex<HashMap<String, Handler>>>,
+    bound: bool,
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+impl fmt::Debug for KeybindHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeybindHandle")
+            .field("bind", &self.bind)
+            .field("token", &self.token)
+            .finish()
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+impl KeybindHandle {
+    pub fn bind(&self) -> &Keybind {
+        &self.bind
+    }
+
+    /// Returns the data of the `custom` event the bind sends.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Removes the handler, returns the unbind command if the bind wasn't removed yet.
+    fn remove(&mut self) -> Option<Command> {
+        self.handlers.lock().unwrap().remove(&self.token);
+        std::mem::take(&mut self.bound).then(|| self.bind.unbind_command())
+    }
+
+    /// Removes the bind.
+    #[cfg(feature = "async")]
+    pub async fn unbind(mut self) -> Result<(), CommandError> {
+        match self.remove() {
+            Some(command) => self.conn.send_command(&command).await,
+            None => Ok(()),
+        }
+    }
+
+    /// The blocking counterpart of [`Self::unbind`].
+    #[cfg(feature = "sync")]
+    pub fn unbind_sync(mut self) -> Result<(), CommandError> {
+        match self.remove() {
+            Some(command) => self.conn.send_command_sync(&command),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+impl Drop for KeybindHandle {
+    /// Removes the bind with a blocking request, along with any other bind of the same keys.
+    fn drop(&mut self) {
+        if let Some(command) = self.remove() {
+            let _ = self.conn.send_recipe_blocking(&[command]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod keybinds_tests {
+    use super::Keybind;
+    use crate::arguments::{KeyArgument, ModArgument};
+
+    #[test]
+    fn test_keybind_commands() {
+        let bind = Keybind::new(
+            &[ModArgument::Super, ModArgument::Shift],
+            KeyArgument::Char('q'),
+        )
+        .with_locked(true)
+        .with_repeat(true);
+        assert_eq!(
+            bind.bind_command("exec", "kitty").get_command(),
+            "keyword bindel SUPER SHIFT,q,exec,kitty"
+        );
+        assert_eq!(
+            Keybind::new(&[], KeyArgument::Code(36))
+                .unbind_command()
+                .get_command(),
+            "keyword unbind ,code:36"
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_keybind_handlers() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use super::Keybinds;
+        use crate::events::HyprlandEvent;
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("binds", "[]");
+        let conn = mock.connection();
+        let keybinds = Keybinds::new();
+
+        let presses = Arc::new(AtomicUsize::new(0));
+        let handle = {
+            let presses = presses.clone();
+            keybinds
+                .bind_sync(
+                    &conn,
+                    Keybind::new(&[ModArgument::Super], KeyArgument::Char('p')),
+                    move || {
+                        presses.fetch_add(1, Ordering::Relaxed);
+                    },
+                )
+                .unwrap()
+        };
+        let pressed = HyprlandEvent::Custom {
+            data: handle.token().to_string(),
+        };
+
+        assert!(keybinds.handle_event(&pressed));
+        assert!(keybinds.clone().handle_event(&pressed));
+        assert!(!keybinds.handle_event(&HyprlandEvent::Custom {
+            data: "other".to_string()
+        }));
+        assert_eq!(presses.load(Ordering::Relaxed), 2);
+
+        drop(handle);
+        assert!(!keybinds.handle_event(&pressed));
+        assert_eq!(mock.received()[2..], ["keyword unbind SUPER,p".to_string()]);
+        assert!(mock.received()[1].starts_with("keyword bind SUPER,p,event,hyprrust-bind-"));
+
+        mock.reply("keyword unbind ,code:36", "error");
+        let handle = keybinds
+            .bind_sync(&conn, Keybind::new(&[], KeyArgument::Code(36)), || {})
+            .unwrap();
+        assert!(handle.unbind_sync().is_err());
+        assert_eq!(mock.received().len(), 6);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_bound_keys_are_refused() {
+        use super::Keybinds;
+        use crate::errors::KeybindError;
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply(
+            "binds",
+            r#"[
+                {"modmask": 64, "key": "Q", "keycode": 0, "dispatcher": "killactive", "arg": ""},
+                {"modmask": 0, "key": "", "keycode": 36, "dispatcher": "exec", "arg": "kitty"},
+                {"modmask": 64, "key": "mouse:272", "keycode": 0, "dispatcher": "movewindow", "arg": ""}
+            ]"#,
+        );
+        let conn = mock.connection();
+        let keybinds = Keybinds::new();
+
+        for bind in [
+            Keybind::new(&[ModArgument::Super], KeyArgument::Char('q')),
+            Keybind::new(&[], KeyArgument::Code(36)),
+            Keybind::new(&[ModArgument::Super], KeyArgument::Mouse(272)),
+        ] {
+            assert!(matches!(
+                keybinds.bind_sync(&conn, bind, || {}),
+                Err(KeybindError::AlreadyBound(_))
+            ));
+        }
+        assert_eq!(mock.received(), ["binds", "binds", "binds"]);
+
+        let bind = Keybind::new(
+            &[ModArgument::Super, ModArgument::Shift],
+            KeyArgument::Char('q'),
+        );
+        let handle = keybinds.bind_sync(&conn, bind, || {}).unwrap();
+        drop(handle);
+        assert_eq!(mock.received()[5], "keyword unbind SUPER SHIFT,q");
+    }
+}
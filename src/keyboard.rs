@@ -0,0 +1,324 @@
+//! Keyboard layouts of every keyboard, kept up to date using `activelayout` events.
+//!
+//! [`KeyboardLayouts`] is seeded with the keyboards reported by Hyprland and updated by feeding it
+//! events, like [`HyprlandState`], or by letting [`KeyboardLayouts::watch`] listen for them. Layouts
+//! are switched with [`switch_xkb_layout`].
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use hyprrust::arguments::KeyboardArgument;
+//! use hyprrust::keyboard::KeyboardLayouts;
+//! use hyprrust::HyprlandConnection;
+//!
+//! let mut conn = HyprlandConnection::current()?;
+//! let mut layouts = KeyboardLayouts::new(&conn).await?;
+//! println!("{:?}", layouts.main_keyboard().map(|k| &k.active_keymap));
+//!
+//! layouts
+//!     .watch(&mut conn, KeyboardArgument::Main, |keyboard| {
+//!         println!("{}", keyboard.active_keymap)
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`HyprlandState`]: crate::state::HyprlandState
+//! [`switch_xkb_layout`]: crate::commands::switch_xkb_layout
+
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::io;
+
+use crate::data::{Devices, Keyboard};
+use crate::events::HyprlandEvent;
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::{
+    arguments::KeyboardArgument,
+    events::{EventFilter, HyprlandEventType},
+    HyprlandConnection,
+};
+
+/// The keyboards of a Hyprland instance and their active layouts.
+#[derive(Debug, Clone, Default)]
+pub struct KeyboardLayouts {
+    keyboards: Vec<Keyboard>,
+    needs_resync: bool,
+}
+
+impl KeyboardLayouts {
+    /// Creates a new list seeded with the keyboards currently reported by Hyprland.
+    #[cfg(feature = "async")]
+    pub async fn new(conn: &HyprlandConnection) -> Result<Self, io::Error> {
+        let mut layouts = KeyboardLayouts::default();
+        layouts.resync(conn).await?;
+        Ok(layouts)
+    }
+
+    /// The blocking counterpart of [`Self::new`].
+    #[cfg(feature = "sync")]
+    pub fn new_sync(conn: &HyprlandConnection) -> Result<Self, io::Error> {
+        let mut layouts = KeyboardLayouts::default();
+        layouts.resync_sync(conn)?;
+        Ok(layouts)
+    }
+
+    /// Throws away everything known and reloads the keyboards from Hyprland.
+    #[cfg(feature = "async")]
+    pub async fn resync(&mut self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        self.load(conn.get::<Devices>().await?);
+        Ok(())
+    }
+
+    /// The blocking counterpart of [`Self::resync`].
+    #[cfg(feature = "sync")]
+    pub fn resync_sync(&mut self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        self.load(conn.get_sync::<Devices>()?);
+        Ok(())
+    }
+
+    fn load(&mut self, devices: Devices) {
+        self.keyboards = devices.keyboards;
+        self.needs_resync = false;
+    }
+
+    /// Returns whether a keyboard was added or the config was reloaded, so the list should be
+    /// reloaded.
+    pub fn needs_resync(&self) -> bool {
+        self.needs_resync
+    }
+
+    /// Updates the active layout using `event` and returns the keyboard that changed. Events that
+    /// are not relevant are ignored.
+    pub fn apply(&mut self, event: &HyprlandEvent) -> Option<&Keyboard> {
+        match event {
+            HyprlandEvent::ActiveLayout {
+                keyboard_name,
+                layout_name,
+            } => {
+                let Some(index) = self
+                    .keyboards
+                    .iter()
+                    .position(|keyboard| &keyboard.name == keyboard_name)
+                else {
+                    self.needs_resync = true;
+                    return None;
+                };
+
+                let keyboard = &mut self.keyboards[index];
+                keyboard.active_keymap = layout_name.clone();
+                Some(keyboard)
+            }
+            // The configured layouts might have changed
+            HyprlandEvent::ConfigReloaded => {
+                self.needs_resync = true;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Listens for layout changes and calls `on_change` with every keyboard matching `keyboard`
+    /// whose layout changed. The keyboards are reloaded when needed. Returns when the event socket
+    /// is closed.
+    #[cfg(feature = "async")]
+    pub async fn watch<F>(
+        &mut self,
+        conn: &mut HyprlandConnection,
+        keyboard: KeyboardArgument,
+        mut on_change: F,
+    ) -> Result<(), io::Error>
+    where
+        F: FnMut(&Keyboard),
+    {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let mut rx = conn.listen_to_events(layout_filter()).await?;
+
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    if self.apply(&event).is_none() && self.needs_resync {
+                        self.resync(conn).await?;
+                    }
+                    self.notify(&event, &keyboard, &mut on_change);
+                }
+                Err(RecvError::Lagged(_)) => self.resync(conn).await?,
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+
+    /// The blocking counterpart of [`Self::watch`].
+    #[cfg(feature = "sync")]
+    pub fn watch_sync<F>(
+        &mut self,
+        conn: &HyprlandConnection,
+        keyboard: KeyboardArgument,
+        mut on_change: F,
+    ) -> Result<(), io::Error>
+    where
+        F: FnMut(&Keyboard),
+    {
+        use crate::events::single_event_sync::DetachedEventConnection;
+
+        let events = DetachedEventConnection::from_connection(
+            conn.with_instance(conn.instance().to_string()),
+            layout_filter(),
+        )?;
+
+        for event in events.matching() {
+            let event = event?;
+            if self.apply(&event).is_none() && self.needs_resync {
+                self.resync_sync(conn)?;
+            }
+            self.notify(&event, &keyboard, &mut on_change);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn notify<F>(&self, event: &HyprlandEvent, keyboard: &KeyboardArgument, on_change: &mut F)
+    where
+        F: FnMut(&Keyboard),
+    {
+        if let HyprlandEvent::ActiveLayout { keyboard_name, .. } = event {
+            if let Some(changed) = self
+                .keyboard(keyboard_name)
+                .filter(|changed| keyboard.matches(changed))
+            {
+                on_change(changed);
+            }
+        }
+    }
+
+    /// Returns all known keyboards.
+    pub fn keyboards(&self) -> impl Iterator<Item = &Keyboard> {
+        self.keyboards.iter()
+    }
+
+    /// Returns the keyboard named `name`.
+    pub fn keyboard(&self, name: &str) -> Option<&Keyboard> {
+        self.keyboards.iter().find(|keyboard| keyboard.name == name)
+    }
+
+    /// Returns the main keyboard, the one `switchxkblayout current` switches.
+    pub fn main_keyboard(&self) -> Option<&Keyboard> {
+        self.keyboards.iter().find(|keyboard| keyboard.main)
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+fn layout_filter() -> EventFilter {
+    let mut filter = EventFilter::new_exclude_all();
+    filter.add_event(&HyprlandEventType::ActiveLayout);
+    filter.add_event(&HyprlandEventType::ConfigReloaded);
+    filter
+}
+
+#[cfg(test)]
+mod keyboard_tests {
+    use super::KeyboardLayouts;
+    use crate::arguments::KeyboardArgument;
+    use crate::data::{Devices, HyprlandData};
+    use crate::events::HyprlandEvent;
+
+    const DEVICES: &str = r#"{
+        "mice": [], "tablets": [], "touch": [], "switches": [],
+        "keyboards": [{
+            "address": "0x1", "name": "at-translated-set-2-keyboard", "rules": "", "model": "",
+            "layout": "us,de", "variant": ",nodeadkeys", "options": "grp:alt_shift_toggle",
+            "active_keymap": "English (US)", "capsLock": false, "numLock": true, "main": true
+        }, {
+            "address": "0x2", "name": "yubikey", "rules": "", "model": "", "layout": "us",
+            "variant": "", "options": "", "active_keymap": "English (US)", "capsLock": false,
+            "numLock": false, "main": false
+        }]
+    }"#;
+
+    #[test]
+    fn test_layouts_follow_events() {
+        let mut layouts = KeyboardLayouts::default();
+        layouts.load(Devices::from_reply(DEVICES).unwrap());
+
+        let main = layouts.main_keyboard().unwrap();
+        assert_eq!(main.layouts(), ["us", "de"]);
+        assert_eq!(main.variants(), ["", "nodeadkeys"]);
+
+        let keyboard = layouts
+            .apply(&HyprlandEvent::ActiveLayout {
+                keyboard_name: "at-translated-set-2-keyboard".to_string(),
+                layout_name: "German (no dead keys)".to_string(),
+            })
+            .unwrap();
+        assert!(KeyboardArgument::Main.matches(keyboard));
+        assert!(!KeyboardArgument::Name("yubikey".to_string()).matches(keyboard));
+        assert_eq!(
+            layouts
+                .keyboard("at-translated-set-2-keyboard")
+                .unwrap()
+                .active_keymap,
+            "German (no dead keys)"
+        );
+        assert_eq!(
+            layouts.keyboard("yubikey").unwrap().active_keymap,
+            "English (US)"
+        );
+
+        assert!(layouts
+            .apply(&HyprlandEvent::ActiveLayout {
+                keyboard_name: "new-keyboard".to_string(),
+                layout_name: "English (US)".to_string(),
+            })
+            .is_none());
+        assert!(layouts.needs_resync());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_watch_keyboard() {
+        use std::sync::mpsc;
+        use std::thread;
+        use std::time::Duration;
+
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("devices", DEVICES);
+        let conn = mock.connection();
+        let mut layouts = KeyboardLayouts::new_sync(&conn).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let watcher = thread::spawn(move || {
+            layouts.watch_sync(&conn, KeyboardArgument::Main, |keyboard| {
+                tx.send(keyboard.active_keymap.clone()).unwrap();
+            })
+        });
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        mock.push_event("activelayout>>yubikey,German");
+        mock.push_event("activelayout>>at-translated-set-2-keyboard,German (no dead keys)");
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(1)).unwrap(),
+            "German (no dead keys)"
+        );
+
+        // Unknown keyboards are only reported after the keyboards are reloaded
+        mock.reply(
+            "devices",
+            &DEVICES.replace("yubikey", "new-keyboard").replace(
+                r#""numLock": false, "main": false"#,
+                r#""numLock": false, "main": true"#,
+            ),
+        );
+        mock.push_event("activelayout>>new-keyboard,English (US)");
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(1)).unwrap(),
+            "English (US)"
+        );
+
+        mock.disconnect_event_listeners();
+        assert!(watcher.join().unwrap().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,647 @@
+//! Launching programs and waiting for the window they open.
+//!
+//! Hyprland doesn't tell which window a command like `exec` opened, so the functions in this
+//! module watch the `openwindow` events and return the first new window that matches a
+//! [`LaunchMatcher`]. [`RunOrRaise`] builds on the same matcher to focus a window that is already
+//! open and only launch the program when there is none.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//!
+//! use hyprrust::commands::prelude::*;
+//! use hyprrust::launch::LaunchMatcher;
+//! use hyprrust::HyprlandConnection;
+//!
+//! let conn = HyprlandConnection::current()?;
+//!
+//! let window = conn
+//!     .launch(
+//!         &exec(ExecRules::new(), "kitty --class scratch".to_string())?,
+//!         &LaunchMatcher::new().with_class("scratch"),
+//!         Duration::from_secs(5),
+//!     )
+//!     .await?;
+//! conn.send_command(&set_floating(window.into())).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::process::{self, Child};
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+use crate::commands::Command;
+use crate::data::{CurrentWorkspace, Window, Windows};
+use crate::errors::LaunchError;
+use crate::events::{EventFilter, HyprlandEventType};
+use crate::HyprlandConnection;
+
+type WindowPredicate = Box<dyn Fn(&Window) -> bool + Send + Sync>;
+
+/// Decides which window belongs to the launched program. Every criteria that is set has to
+/// match, an empty matcher matches the first window that opens.
+#[derive(Default)]
+pub struct LaunchMatcher {
+    pid: Option<i32>,
+    class: Option<String>,
+    title: Option<String>,
+    tag: Option<String>,
+    predicate: Option<WindowPredicate>,
+}
+
+impl LaunchMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches windows owned by the process `pid`. Hyprland runs `exec` through a shell, so the
+    /// pid is usually only known when using [`HyprlandConnection::launch_process`].
+    pub fn with_pid(mut self, pid: i32) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    /// Matches windows whose class or initial class is `class`.
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_string());
+        self
+    }
+
+    /// Matches windows whose title or initial title contains `title`.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Matches windows tagged with `tag`, including dynamic tags.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
+    /// Matches windows for which `predicate` returns true.
+    pub fn with_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Window) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Returns whether `window` matches.
+    pub fn matches(&self, window: &Window) -> bool {
+        if self.pid.is_some_and(|pid| pid != window.pid) {
+            return false;
+        }
+        if self
+            .class
+            .as_ref()
+            .is_some_and(|class| class != &window.class && class != &window.initial_class)
+        {
+            return false;
+        }
+        if self.title.as_ref().is_some_and(|title| {
+            !window.title.contains(title.as_str()) && !window.initial_title.contains(title.as_str())
+        }) {
+            return false;
+        }
+        if let Some(tag) = self.tag.as_ref() {
+            // Dynamic tags are listed with a trailing `*`
+            if !window
+                .tags
+                .iter()
+                .any(|t| t.strip_suffix('*').unwrap_or(t) == tag)
+            {
+                return false;
+            }
+        }
+        if self
+            .predicate
+            .as_ref()
+            .is_some_and(|predicate| !predicate(window))
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl fmt::Debug for LaunchMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LaunchMatcher")
+            .field("pid", &self.pid)
+            .field("class", &self.class)
+            .field("title", &self.title)
+            .field("tag", &self.tag)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What [`HyprlandConnection::run_or_raise`] did.
+#[derive(Debug, Clone)]
+pub enum RaiseOutcome {
+    /// An open window matched and was focused
+    Raised(Box<Window>),
+    /// No window matched, so the command was sent
+    Launched,
+}
+
+/// Focuses a window matching a [`LaunchMatcher`], or sends a command, usually an [`exec`], when
+/// none is open.
+///
+/// When several windows match, repeated calls cycle through them: if the focused window matches,
+/// the next one is raised, otherwise the most recently focused match is.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use hyprrust::commands::prelude::*;
+/// use hyprrust::launch::{LaunchMatcher, RunOrRaise};
+/// use hyprrust::HyprlandConnection;
+///
+/// let conn = HyprlandConnection::current()?;
+///
+/// let firefox = RunOrRaise::new(
+///     exec(ExecRules::new(), "firefox".to_string())?,
+///     LaunchMatcher::new().with_class("firefox"),
+/// )
+/// .with_bring_to_current_workspace(true);
+/// conn.run_or_raise(&firefox).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`exec`]: crate::commands::exec
+#[derive(Debug)]
+pub struct RunOrRaise {
+    command: Command,
+    matcher: LaunchMatcher,
+    bring_to_current_workspace: bool,
+}
+
+impl RunOrRaise {
+    pub fn new(command: Command, matcher: LaunchMatcher) -> Self {
+        RunOrRaise {
+            command,
+            matcher,
+            bring_to_current_workspace: false,
+        }
+    }
+
+    /// Moves the raised window to the current workspace instead of switching to its workspace.
+    pub fn with_bring_to_current_workspace(mut self, bring: bool) -> Self {
+        self.bring_to_current_workspace = bring;
+        self
+    }
+
+    /// Picks the window to raise and returns it with the commands that raise it.
+    fn raise_commands(
+        &self,
+        windows: &Windows,
+        workspace: &CurrentWorkspace,
+    ) -> Option<(Window, Vec<Command>)> {
+        let mut matching: Vec<&Window> = windows
+            .iter()
+            .filter(|window| self.matcher.matches(window))
+            .collect();
+        matching.sort_by(|a, b| a.address.cmp(&b.address));
+
+        let window = match matching
+            .iter()
+            .position(|window| window.focus_history_id == 0)
+        {
+            Some(focused) => matching[(focused + 1) % matching.len()],
+            None => *matching
+                .iter()
+                .min_by_key(|window| window.focus_history_id)?,
+        };
+
+        let mut commands = Vec::new();
+        if self.bring_to_current_workspace && window.workspace.id != workspace.id {
+            commands.push(move_to_workspace_silent(
+                WorkspaceArgument::ID(workspace.id),
+                window.into(),
+            ));
+        }
+        commands.push(focus_window(window.into()));
+
+        Some((window.clone(), commands))
+    }
+}
+
+fn open_window_filter() -> EventFilter {
+    let mut filter = EventFilter::new_exclude_all();
+    filter.add_event(&HyprlandEventType::OpenWindow);
+    filter
+}
+
+fn addresses(windows: &Windows) -> HashSet<String> {
+    windows
+        .iter()
+        .map(|window| window.address.clone())
+        .collect()
+}
+
+/// Returns the first window that is not in `known` and matches both `matcher` and `pid`.
+fn find_new_window(
+    windows: Windows,
+    known: &HashSet<String>,
+    matcher: &LaunchMatcher,
+    pid: Option<i32>,
+) -> Option<Window> {
+    windows
+        .iter()
+        .find(|window| {
+            if known.contains(&window.address) || pid.is_some_and(|pid| pid != window.pid) {
+                return false;
+            }
+            matcher.matches(window)
+        })
+        .cloned()
+}
+
+fn closed_error() -> LaunchError {
+    io::Error::new(io::ErrorKind::ConnectionAborted, "The event socket closed").into()
+}
+
+impl HyprlandConnection {
+    /// Sends `command`, usually an [`exec`], and waits for the first window that opens after it
+    /// and matches `matcher`. Returns [`LaunchError::Timeout`] if no window matches before
+    /// `timeout`.
+    ///
+    /// [`exec`]: crate::commands::exec
+    #[cfg(feature = "async")]
+    pub async fn launch(
+        &self,
+        command: &Command,
+        matcher: &LaunchMatcher,
+        timeout: Duration,
+    ) -> Result<Window, LaunchError> {
+        let mut events = self.with_instance(self.instance().to_string());
+        let mut rx = events
+            .listen_to_events_with_policy(
+                open_window_filter(),
+                crate::events::ReconnectPolicy::never(),
+            )
+            .await?;
+
+        let result = async {
+            let known = addresses(&self.get::<Windows>().await?);
+            self.send_command(command).await?;
+            self.wait_for_new_window(&mut rx, &known, matcher, None, timeout)
+                .await
+        }
+        .await;
+
+        events.stop_listening();
+        result
+    }
+
+    /// Spawns `process` and waits for the first window it opens that also matches `matcher`. The
+    /// window has to belong to the spawned process itself, not to one of its children.
+    ///
+    /// The process keeps running if no window opens before `timeout`.
+    #[cfg(feature = "async")]
+    pub async fn launch_process(
+        &self,
+        process: &mut process::Command,
+        matcher: &LaunchMatcher,
+        timeout: Duration,
+    ) -> Result<(Child, Window), LaunchError> {
+        let mut events = self.with_instance(self.instance().to_string());
+        let mut rx = events
+            .listen_to_events_with_policy(
+                open_window_filter(),
+                crate::events::ReconnectPolicy::never(),
+            )
+            .await?;
+
+        let result = async {
+            let known = addresses(&self.get::<Windows>().await?);
+            let child = process.spawn()?;
+            let pid = Some(child.id() as i32);
+            let window = self
+                .wait_for_new_window(&mut rx, &known, matcher, pid, timeout)
+                .await?;
+            Ok((child, window))
+        }
+        .await;
+
+        events.stop_listening();
+        result
+    }
+
+    #[cfg(feature = "async")]
+    async fn wait_for_new_window(
+        &self,
+        rx: &mut tokio::sync::broadcast::Receiver<crate::events::HyprlandEvent>,
+        known: &HashSet<String>,
+        matcher: &LaunchMatcher,
+        pid: Option<i32>,
+        timeout: Duration,
+    ) -> Result<Window, LaunchError> {
+        use tokio::sync::broadcast::error::RecvError;
+        use tokio::time;
+
+        let deadline = time::Instant::now() + timeout;
+        loop {
+            match time::timeout_at(deadline, rx.recv()).await {
+                Err(_) => return Err(LaunchError::Timeout),
+                Ok(Err(RecvError::Closed)) => return Err(closed_error()),
+                // A lagged receiver might have missed the window, so look for it anyway
+                Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => {}
+            }
+
+            let windows = self.get::<Windows>().await?;
+            if let Some(window) = find_new_window(windows, known, matcher, pid) {
+                return Ok(window);
+            }
+        }
+    }
+
+    /// Focuses the window matched by `run_or_raise`, or sends its command when no window matches.
+    /// See [`RunOrRaise`].
+    #[cfg(feature = "async")]
+    pub async fn run_or_raise(
+        &self,
+        run_or_raise: &RunOrRaise,
+    ) -> Result<RaiseOutcome, LaunchError> {
+        let (windows, workspace) = self.get_batch::<(Windows, CurrentWorkspace)>().await?;
+
+        match run_or_raise.raise_commands(&windows, &workspace) {
+            Some((window, commands)) => {
+                for command in &commands {
+                    self.send_command(command).await?;
+                }
+                Ok(RaiseOutcome::Raised(Box::new(window)))
+            }
+            None => {
+                self.send_command(&run_or_raise.command).await?;
+                Ok(RaiseOutcome::Launched)
+            }
+        }
+    }
+
+    /// The blocking counterpart of [`Self::launch`].
+    #[cfg(feature = "sync")]
+    pub fn launch_sync(
+        &self,
+        command: &Command,
+        matcher: &LaunchMatcher,
+        timeout: Duration,
+    ) -> Result<Window, LaunchError> {
+        use crate::events::single_event_sync::DetachedEventConnection;
+
+        let events = DetachedEventConnection::from_connection(
+            self.with_instance(self.instance().to_string()),
+            open_window_filter(),
+        )?;
+        let known = addresses(&self.get_sync::<Windows>()?);
+        self.send_command_sync(command)?;
+
+        self.wait_for_new_window_sync(events, &known, matcher, None, timeout)
+    }
+
+    /// The blocking counterpart of [`Self::launch_process`].
+    #[cfg(feature = "sync")]
+    pub fn launch_process_sync(
+        &self,
+        process: &mut process::Command,
+        matcher: &LaunchMatcher,
+        timeout: Duration,
+    ) -> Result<(Child, Window), LaunchError> {
+        use crate::events::single_event_sync::DetachedEventConnection;
+
+        let events = DetachedEventConnection::from_connection(
+            self.with_instance(self.instance().to_string()),
+            open_window_filter(),
+        )?;
+        let known = addresses(&self.get_sync::<Windows>()?);
+        let child = process.spawn()?;
+        let pid = Some(child.id() as i32);
+
+        let window = self.wait_for_new_window_sync(events, &known, matcher, pid, timeout)?;
+        Ok((child, window))
+    }
+
+    /// The blocking counterpart of [`Self::run_or_raise`].
+    #[cfg(feature = "sync")]
+    pub fn run_or_raise_sync(
+        &self,
+        run_or_raise: &RunOrRaise,
+    ) -> Result<RaiseOutcome, LaunchError> {
+        let (windows, workspace) = self.get_batch_sync::<(Windows, CurrentWorkspace)>()?;
+
+        match run_or_raise.raise_commands(&windows, &workspace) {
+            Some((window, commands)) => {
+                for command in &commands {
+                    self.send_command_sync(command)?;
+                }
+                Ok(RaiseOutcome::Raised(Box::new(window)))
+            }
+            None => {
+                self.send_command_sync(&run_or_raise.command)?;
+                Ok(RaiseOutcome::Launched)
+            }
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    fn wait_for_new_window_sync(
+        &self,
+        mut events: crate::events::single_event_sync::DetachedEventConnection,
+        known: &HashSet<String>,
+        matcher: &LaunchMatcher,
+        pid: Option<i32>,
+        timeout: Duration,
+    ) -> Result<Window, LaunchError> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return Err(LaunchError::Timeout);
+            }
+
+            events.set_read_timeout(Some(remaining))?;
+            match events.next_matching() {
+                None => return Err(closed_error()),
+                Some(Err(e))
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    return Err(LaunchError::Timeout)
+                }
+                Some(Err(e)) => return Err(e.into()),
+                Some(Ok(_)) => {}
+            }
+
+            let windows = self.get_sync::<Windows>()?;
+            if let Some(window) = find_new_window(windows, known, matcher, pid) {
+                return Ok(window);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod launch_tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{LaunchMatcher, RaiseOutcome, RunOrRaise};
+    use crate::commands::prelude::*;
+    use crate::errors::LaunchError;
+    use crate::testing::MockHyprland;
+
+    fn window(address: &str, class: &str) -> String {
+        window_on(address, class, 1, 0, "")
+    }
+
+    fn window_on(address: &str, class: &str, workspace: i64, focus: i32, tags: &str) -> String {
+        format!(
+            r#"{{
+                "address": "{address}", "mapped": true, "hidden": false, "at": [0, 0],
+                "size": [100, 100], "workspace": {{"id": {workspace}, "name": "{workspace}"}},
+                "floating": false, "pseudo": false, "monitor": 0, "class": "{class}",
+                "title": "title", "initialClass": "{class}", "initialTitle": "title", "pid": 42,
+                "xwayland": false, "pinned": false, "fullscreen": 0, "fullscreenClient": 0,
+                "grouped": [], "tags": [{tags}], "swallowing": "0x0", "focusHistoryID": {focus},
+                "inhibitingIdle": false
+            }}"#
+        )
+    }
+
+    /// Opens two windows once the mock receives `dispatch exec kitty`.
+    fn open_windows_after_exec(mock: &MockHyprland) {
+        for _ in 0..200 {
+            if mock.received().contains(&"dispatch exec kitty".to_string()) {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let windows = [
+            window("0xaa", "kitty"),
+            window("0xbb", "firefox"),
+            window("0xcc", "kitty"),
+        ];
+        mock.reply("clients", &format!("[{}]", windows.join(",")));
+        mock.push_event("openwindow>>bb,1,firefox,title");
+        mock.push_event("openwindow>>cc,1,kitty,title");
+    }
+
+    #[test]
+    fn test_launch() {
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("clients", &format!("[{}]", window("0xaa", "kitty")));
+        let command = exec(ExecRules::new(), "kitty".to_string()).unwrap();
+        let matcher = LaunchMatcher::new().with_class("kitty").with_pid(42);
+
+        thread::scope(|s| {
+            s.spawn(|| open_windows_after_exec(&mock));
+            let window = mock
+                .connection()
+                .launch_sync(&command, &matcher, Duration::from_secs(5))
+                .unwrap();
+            assert_eq!(window.address, "0xcc");
+        });
+
+        #[cfg(feature = "async")]
+        {
+            mock.reply("clients", &format!("[{}]", window("0xaa", "kitty")));
+            mock.clear_received();
+
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            thread::scope(|s| {
+                s.spawn(|| open_windows_after_exec(&mock));
+                let conn = mock.connection();
+                let window = runtime
+                    .block_on(conn.launch(&command, &matcher, Duration::from_secs(5)))
+                    .unwrap();
+                assert_eq!(window.address, "0xcc");
+            });
+        }
+    }
+
+    #[test]
+    fn test_launch_timeout() {
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("clients", "[]");
+
+        let result = mock.connection().launch_sync(
+            &execr("true".to_string()),
+            &LaunchMatcher::new(),
+            Duration::from_millis(50),
+        );
+        assert!(matches!(result, Err(LaunchError::Timeout)));
+    }
+
+    #[test]
+    fn test_run_or_raise() {
+        let mock = MockHyprland::start().unwrap();
+        let conn = mock.connection();
+        mock.reply(
+            "activeworkspace",
+            r#"{"id": 1, "name": "1", "monitor": "DP-1", "windows": 1, "hasfullscreen": false,
+                "lastwindow": "0xaa", "lastwindowtitle": "title"}"#,
+        );
+        let clients = |focused: &str| {
+            let focus = |address: &str, history: i32| if address == focused { 0 } else { history };
+            let windows = [
+                window_on("0xaa", "kitty", 1, focus("0xaa", 1), r#""term*""#),
+                window_on("0xbb", "firefox", 2, focus("0xbb", 2), ""),
+                window_on("0xcc", "firefox", 3, focus("0xcc", 3), ""),
+            ];
+            format!("[{}]", windows.join(","))
+        };
+
+        let firefox = RunOrRaise::new(
+            execr("firefox".to_string()),
+            LaunchMatcher::new().with_class("firefox"),
+        )
+        .with_bring_to_current_workspace(true);
+
+        // The most recently focused match is raised first, then the matches are cycled through
+        for (focused, raised) in [("0xaa", "0xbb"), ("0xbb", "0xcc"), ("0xcc", "0xbb")] {
+            mock.reply("clients", &clients(focused));
+            mock.clear_received();
+            let outcome = conn.run_or_raise_sync(&firefox).unwrap();
+            assert!(matches!(outcome, RaiseOutcome::Raised(window) if window.address == raised));
+            assert_eq!(
+                mock.received()[2..],
+                [
+                    format!("dispatch movetoworkspacesilent 1,address:{}", raised),
+                    format!("dispatch focuswindow address:{}", raised),
+                ]
+            );
+        }
+
+        // Windows already on the current workspace are only focused
+        mock.reply("clients", &clients("0xbb"));
+        mock.clear_received();
+        let terminal = RunOrRaise::new(
+            execr("kitty".to_string()),
+            LaunchMatcher::new().with_tag("term"),
+        )
+        .with_bring_to_current_workspace(true);
+        conn.run_or_raise_sync(&terminal).unwrap();
+        assert_eq!(mock.received()[2..], ["dispatch focuswindow address:0xaa"]);
+
+        mock.clear_received();
+        let editor = RunOrRaise::new(
+            execr("code".to_string()),
+            LaunchMatcher::new().with_class("code"),
+        );
+        let outcome = conn.run_or_raise_sync(&editor).unwrap();
+        assert!(matches!(outcome, RaiseOutcome::Launched));
+        assert_eq!(mock.received()[2..], ["dispatch execr code"]);
+    }
+}
@@ -16,6 +16,13 @@
 //! default features and include only the `sync` feature if you don't need `async`, this way
 //! `tokio` is not included.
 //!
+//! The `cli` feature builds `hyprrust`, a `hyprctl` like binary that exposes every command and
+//! data type of this crate:
+//! ```sh
+//! cargo install hyprrust --features cli
+//! hyprrust command focus-window class:kitty
+//! ```
+//!
 //! To get started add this crate to your project:
 //! ```sh
 //! cargo add hyprrust
@@ -26,18 +33,41 @@
 //! This crate conatins several modules:
 //!  - [`commands`] All implemented commands, but doesn't include arguments.
 //!  - [`commands::prelude`] All commands and arguments.
+//!  - [`config`] Typed config options, checked against the schema reported by Hyprland.
 //!  - [`data`] All commands used to request data from Hyprland.
 //!  - [`errors`] All errors returned by the API of this library.
 //!  - [`events`] Contains everything needed to receive sync and async events and filter them.
+//!  - [`keybinds`] Keybinds added at runtime that call Rust closures.
+//!  - [`keyboard`] The keyboard layouts of every keyboard, kept up to date using events.
+//!  - [`launch`] Launching programs and waiting for the window they open.
+//!  - [`monitors`] Monitor configurations, and profiles that apply them when monitors are plugged
+//!    in or out.
+//!  - [`notifications`] Showing notifications and dismissing the ones that were shown.
+//!  - `rules` Rules that send commands when windows are opened, closed, moved or renamed
+//!    (needs the `rules` feature).
+//!  - [`state`] A local copy of the compositor state that is kept up to date using events.
+//!  - `testing` A fake Hyprland instance for testing code without a running compositor (needs
+//!    the `testing` feature).
 //!
 //! ### Examples
 //!
 //! See the `examples` folder in the crates source.
 
+pub mod config;
 mod connection;
 mod ctl;
 pub mod errors;
 pub mod events;
+pub mod keybinds;
+pub mod keyboard;
+pub mod launch;
+pub mod monitors;
+pub mod notifications;
+#[cfg(feature = "rules")]
+pub mod rules;
+pub mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub use connection::HyprlandConnection;
 
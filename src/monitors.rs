@@ -0,0 +1,919 @@
+//! Configuring monitors without formatting `monitor` keywords by hand.
+//!
+//! A [`MonitorConfig`] can be built from scratch or from a [`Monitor`] returned by Hyprland, and
+//! turned into the `keyword monitor` command that applies it. [`MonitorProfiles`] applies
+//! configurations for a known set of monitors, whenever that set is connected.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use hyprrust::data::Monitors;
+//! use hyprrust::monitors::{MonitorConfig, MonitorTransform};
+//! use hyprrust::HyprlandConnection;
+//!
+//! let conn = HyprlandConnection::current()?;
+//! let monitors = conn.get::<Monitors>().await?;
+//! let config = MonitorConfig::from(&monitors[0])
+//!     .with_scale(2.0)
+//!     .with_transform(MonitorTransform::Rotate90);
+//! conn.send_command(&config.to_command()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::io;
+
+#[cfg(any(feature = "async", feature = "sync"))]
+use log::warn;
+
+use crate::arguments::{Argument, MonitorArgument, WorkspaceArgument};
+use crate::commands::{move_workspace_to_monitor, set_config_value, Command};
+use crate::data::{Monitor, Workspace};
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::{
+    arguments::MonitorsDataArgument,
+    commands::{RecipeMode, RecipeReport},
+    data::{Monitors, Workspaces},
+    events::{EventFilter, HyprlandEventType},
+    HyprlandConnection,
+};
+
+/// The resolution and refresh rate of a monitor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MonitorMode {
+    Preferred,
+    /// The highest resolution
+    HighRes,
+    /// The highest refresh rate
+    HighRefreshRate,
+    /// The widest mode
+    MaxWidth,
+    Resolution {
+        width: i32,
+        height: i32,
+        refresh_rate: Option<f64>,
+    },
+}
+
+impl fmt::Display for MonitorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Preferred => write!(f, "preferred"),
+            Self::HighRes => write!(f, "highres"),
+            Self::HighRefreshRate => write!(f, "highrr"),
+            Self::MaxWidth => write!(f, "maxwidth"),
+            Self::Resolution {
+                width,
+                height,
+                refresh_rate,
+            } => {
+                write!(f, "{}x{}", width, height)?;
+                if let Some(refresh_rate) = refresh_rate {
+                    // Hyprland reports rates like 143.99600219726562
+                    write!(f, "@{}", (refresh_rate * 1000.0).round() / 1000.0)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Where a monitor is placed in the layout. `Auto*` positions place it next to the monitors that
+/// are already placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorPosition {
+    Auto,
+    AutoRight,
+    AutoLeft,
+    AutoUp,
+    AutoDown,
+    At { x: i32, y: i32 },
+}
+
+impl fmt::Display for MonitorPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::AutoRight => write!(f, "auto-right"),
+            Self::AutoLeft => write!(f, "auto-left"),
+            Self::AutoUp => write!(f, "auto-up"),
+            Self::AutoDown => write!(f, "auto-down"),
+            Self::At { x, y } => write!(f, "{}x{}", x, y),
+        }
+    }
+}
+
+/// The rotation of a monitor, counterclockwise, optionally flipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorTransform {
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl MonitorTransform {
+    const ALL: [MonitorTransform; 8] = [
+        Self::Normal,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::Flipped,
+        Self::Flipped90,
+        Self::Flipped180,
+        Self::Flipped270,
+    ];
+
+    /// Returns the transform with the number used by Hyprland, ie. in [`Monitor::transform`].
+    pub fn from_index(index: i32) -> Option<Self> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| Self::ALL.get(index).copied())
+    }
+
+    pub fn index(&self) -> i32 {
+        *self as i32
+    }
+}
+
+/// When variable refresh rate is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VrrMode {
+    Off,
+    On,
+    Fullscreen,
+    /// Only for fullscreen windows that report showing a game or video
+    FullscreenGame,
+}
+
+/// The configuration of a monitor, as set by the `monitor` keyword. By default the monitor uses
+/// its preferred mode, is placed automatically and is scaled automatically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorConfig {
+    name: String,
+    mode: MonitorMode,
+    position: MonitorPosition,
+    scale: Option<f64>,
+    transform: Option<MonitorTransform>,
+    mirror: Option<String>,
+    bitdepth: Option<u8>,
+    vrr: Option<VrrMode>,
+    disabled: bool,
+}
+
+impl MonitorConfig {
+    /// Creates the configuration of the monitor named `name`, ie. `DP-1`. Hyprland also accepts
+    /// `desc:` followed by the description of the monitor.
+    pub fn new(name: impl Into<String>) -> Self {
+        MonitorConfig {
+            name: name.into(),
+            mode: MonitorMode::Preferred,
+            position: MonitorPosition::Auto,
+            scale: None,
+            transform: None,
+            mirror: None,
+            bitdepth: None,
+            vrr: None,
+            disabled: false,
+        }
+    }
+
+    /// Sets the name of the monitor.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Returns the configurations of every monitor in `monitors`, including the monitors they
+    /// mirror, which `From<&Monitor>` can't look up.
+    pub fn from_monitors(monitors: &[Monitor]) -> Vec<MonitorConfig> {
+        monitors
+            .iter()
+            .map(|monitor| {
+                let mirror = monitors
+                    .iter()
+                    .find(|other| other.id.to_string() == monitor.mirror_of)
+                    .map(|other| other.name.clone());
+                MonitorConfig {
+                    mirror,
+                    ..MonitorConfig::from(monitor)
+                }
+            })
+            .collect()
+    }
+
+    pub fn with_mode(mut self, mode: MonitorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the resolution, keeping the refresh rate if one was set.
+    pub fn with_resolution(mut self, width: i32, height: i32) -> Self {
+        let refresh_rate = match self.mode {
+            MonitorMode::Resolution { refresh_rate, .. } => refresh_rate,
+            _ => None,
+        };
+        self.mode = MonitorMode::Resolution {
+            width,
+            height,
+            refresh_rate,
+        };
+        self
+    }
+
+    /// Sets the refresh rate, only used when a resolution is set.
+    pub fn with_refresh_rate(mut self, rate: f64) -> Self {
+        if let MonitorMode::Resolution { refresh_rate, .. } = &mut self.mode {
+            *refresh_rate = Some(rate);
+        }
+        self
+    }
+
+    pub fn with_position(mut self, position: MonitorPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn with_auto_scale(mut self) -> Self {
+        self.scale = None;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: MonitorTransform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Mirrors the monitor named `monitor`.
+    pub fn with_mirror(mut self, monitor: impl Into<String>) -> Self {
+        self.mirror = Some(monitor.into());
+        self
+    }
+
+    /// Sets the bits per color, Hyprland supports 8 and 10.
+    pub fn with_bitdepth(mut self, bitdepth: u8) -> Self {
+        self.bitdepth = Some(bitdepth);
+        self
+    }
+
+    pub fn with_vrr(mut self, vrr: VrrMode) -> Self {
+        self.vrr = Some(vrr);
+        self
+    }
+
+    /// Disables the monitor, every other setting is ignored.
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn mode(&self) -> MonitorMode {
+        self.mode
+    }
+
+    pub fn position(&self) -> MonitorPosition {
+        self.position
+    }
+
+    /// Returns the scale, `None` if it's picked automatically.
+    pub fn scale(&self) -> Option<f64> {
+        self.scale
+    }
+
+    pub fn transform(&self) -> Option<MonitorTransform> {
+        self.transform
+    }
+
+    pub fn mirror(&self) -> Option<&str> {
+        self.mirror.as_deref()
+    }
+
+    pub fn bitdepth(&self) -> Option<u8> {
+        self.bitdepth
+    }
+
+    pub fn vrr(&self) -> Option<VrrMode> {
+        self.vrr
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Returns the `keyword monitor` command that applies this configuration.
+    pub fn to_command(&self) -> Command {
+        set_config_value("monitor".to_string(), self.to_string())
+    }
+}
+
+impl fmt::Display for MonitorConfig {
+    /// Formats the configuration as the value of the `monitor` keyword.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.disabled {
+            return write!(f, "{},disable", self.name);
+        }
+
+        write!(f, "{},{},{},", self.name, self.mode, self.position)?;
+        match self.scale {
+            Some(scale) => write!(f, "{}", scale)?,
+            None => write!(f, "auto")?,
+        }
+        if let Some(transform) = self.transform {
+            write!(f, ",transform,{}", transform.index())?;
+        }
+        if let Some(mirror) = &self.mirror {
+            write!(f, ",mirror,{}", mirror)?;
+        }
+        if let Some(bitdepth) = self.bitdepth {
+            write!(f, ",bitdepth,{}", bitdepth)?;
+        }
+        if let Some(vrr) = self.vrr {
+            write!(f, ",vrr,{}", vrr as i32)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Monitor> for MonitorConfig {
+    /// Returns the configuration `monitor` is using right now.
+    ///
+    /// The monitor it mirrors is left out, since Hyprland reports it by id, use
+    /// [`MonitorConfig::from_monitors`] instead. So is VRR, since Hyprland only reports whether
+    /// it's active right now, which isn't the case when it's only used for fullscreen windows.
+    fn from(monitor: &Monitor) -> Self {
+        MonitorConfig {
+            mode: MonitorMode::Resolution {
+                width: monitor.width,
+                height: monitor.height,
+                refresh_rate: Some(monitor.refresh_rate),
+            },
+            position: MonitorPosition::At {
+                x: monitor.x,
+                y: monitor.y,
+            },
+            scale: Some(monitor.scale),
+            transform: MonitorTransform::from_index(monitor.transform),
+            bitdepth: monitor.current_format.contains("2101010").then_some(10),
+            disabled: monitor.disabled,
+            ..MonitorConfig::new(monitor.name.clone())
+        }
+    }
+}
+
+impl From<&MonitorConfig> for Command {
+    fn from(value: &MonitorConfig) -> Self {
+        value.to_command()
+    }
+}
+
+/// Matches monitors by their fields. Every criteria that is set has to be equal to the field of
+/// the monitor, an empty matcher matches all monitors.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonitorMatcher {
+    name: Option<String>,
+    description: Option<String>,
+    make: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+}
+
+impl MonitorMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches the name of the connector, ie. `eDP-1`.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn with_make(mut self, make: &str) -> Self {
+        self.make = Some(make.to_string());
+        self
+    }
+
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_string());
+        self
+    }
+
+    pub fn with_serial(mut self, serial: &str) -> Self {
+        self.serial = Some(serial.to_string());
+        self
+    }
+
+    pub fn matches(&self, monitor: &Monitor) -> bool {
+        let field_matches = |expected: &Option<String>, value: &str| !matches!(expected, Some(expected) if expected != value);
+
+        field_matches(&self.name, &monitor.name)
+            && field_matches(&self.description, &monitor.description)
+            && field_matches(&self.make, &monitor.make)
+            && field_matches(&self.model, &monitor.model)
+            && field_matches(&self.serial, &monitor.serial)
+    }
+}
+
+/// A monitor that is part of a [`MonitorProfile`], with the configuration it gets and the
+/// workspaces bound to it.
+#[derive(Debug, Clone)]
+pub struct ProfileMonitor {
+    matcher: MonitorMatcher,
+    config: MonitorConfig,
+    workspaces: Vec<WorkspaceArgument>,
+}
+
+impl ProfileMonitor {
+    /// The name of `config` is replaced by the name of the monitor that matches `matcher`.
+    pub fn new(matcher: MonitorMatcher, config: MonitorConfig) -> Self {
+        ProfileMonitor {
+            matcher,
+            config,
+            workspaces: Vec::new(),
+        }
+    }
+
+    /// Binds `workspace` to this monitor, moving it there if it's open.
+    pub fn with_workspace(mut self, workspace: WorkspaceArgument) -> Self {
+        self.workspaces.push(workspace);
+        self
+    }
+
+    fn commands(&self, monitor: &Monitor, workspaces: &[Workspace]) -> Vec<Command> {
+        let mut commands = vec![self
+            .config
+            .clone()
+            .with_name(monitor.name.clone())
+            .to_command()];
+
+        for workspace in &self.workspaces {
+            let selector = workspace.to_argument_string();
+            commands.push(set_config_value(
+                "workspace".to_string(),
+                format!("{},monitor:{}", selector, monitor.name),
+            ));
+
+            let is_open = match workspace {
+                WorkspaceArgument::ID(id) => workspaces.iter().any(|open| open.id == *id),
+                WorkspaceArgument::Name(name) => workspaces.iter().any(|open| open.name == *name),
+                _ => true,
+            };
+            if is_open {
+                commands.push(move_workspace_to_monitor(
+                    workspace.clone(),
+                    MonitorArgument::Name(monitor.name.clone()),
+                ));
+            }
+        }
+
+        commands
+    }
+}
+
+/// A set of monitors, that is applied when exactly these monitors are connected.
+#[derive(Debug, Clone)]
+pub struct MonitorProfile {
+    name: String,
+    monitors: Vec<ProfileMonitor>,
+}
+
+impl MonitorProfile {
+    pub fn new(name: impl Into<String>) -> Self {
+        MonitorProfile {
+            name: name.into(),
+            monitors: Vec::new(),
+        }
+    }
+
+    pub fn with_monitor(mut self, monitor: ProfileMonitor) -> Self {
+        self.monitors.push(monitor);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Pairs every monitor of the profile with a different connected monitor, `None` if the
+    /// profile doesn't match the connected monitors.
+    fn assign<'a>(&self, connected: &'a [Monitor]) -> Option<Vec<&'a Monitor>> {
+        fn assign_from<'a>(
+            monitors: &[ProfileMonitor],
+            connected: &'a [Monitor],
+            assigned: &mut Vec<&'a Monitor>,
+        ) -> bool {
+            let Some((first, rest)) = monitors.split_first() else {
+                return true;
+            };
+
+            for monitor in connected {
+                if !first.matcher.matches(monitor)
+                    || assigned.iter().any(|taken| taken.name == monitor.name)
+                {
+                    continue;
+                }
+
+                assigned.push(monitor);
+                if assign_from(rest, connected, assigned) {
+                    return true;
+                }
+                assigned.pop();
+            }
+            false
+        }
+
+        if self.monitors.len() != connected.len() {
+            return None;
+        }
+        let mut assigned = Vec::new();
+        assign_from(&self.monitors, connected, &mut assigned).then_some(assigned)
+    }
+
+    /// Returns whether every connected monitor matches a different monitor of the profile.
+    pub fn matches(&self, connected: &[Monitor]) -> bool {
+        self.assign(connected).is_some()
+    }
+
+    /// Returns the commands that apply the profile, `None` if it doesn't match `connected`.
+    pub fn commands(
+        &self,
+        connected: &[Monitor],
+        workspaces: &[Workspace],
+    ) -> Option<Vec<Command>> {
+        let assigned = self.assign(connected)?;
+        Some(
+            self.monitors
+                .iter()
+                .zip(assigned)
+                .flat_map(|(profile_monitor, monitor)| {
+                    profile_monitor.commands(monitor, workspaces)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Applies the first [`MonitorProfile`] that matches the connected monitors, every time a
+/// monitor is connected or disconnected.
+///
+/// Disabled monitors are still connected, so a profile that disables a monitor keeps matching
+/// after it's applied. Failed commands are logged and don't stop [`Self::run`].
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use hyprrust::arguments::WorkspaceArgument;
+/// use hyprrust::monitors::*;
+/// use hyprrust::HyprlandConnection;
+///
+/// let mut conn = HyprlandConnection::current()?;
+/// let laptop = MonitorMatcher::new().with_name("eDP-1");
+///
+/// let profiles = MonitorProfiles::new()
+///     .with_profile(MonitorProfile::new("undocked").with_monitor(ProfileMonitor::new(
+///         laptop.clone(),
+///         MonitorConfig::new("eDP-1").with_scale(1.5),
+///     )))
+///     .with_profile(
+///         MonitorProfile::new("docked")
+///             .with_monitor(ProfileMonitor::new(
+///                 laptop,
+///                 MonitorConfig::new("eDP-1").with_disabled(true),
+///             ))
+///             .with_monitor(
+///                 ProfileMonitor::new(
+///                     MonitorMatcher::new().with_description("Dell Inc. DELL U2720Q ABC123"),
+///                     MonitorConfig::new("dell").with_resolution(3840, 2160),
+///                 )
+///                 .with_workspace(WorkspaceArgument::ID(1)),
+///             ),
+///     );
+///
+/// profiles.run(&mut conn).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MonitorProfiles {
+    profiles: Vec<MonitorProfile>,
+    /// The profile that was applied last and the monitors that were connected
+    applied: Option<(String, Vec<String>)>,
+}
+
+impl MonitorProfiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_profile(&mut self, profile: MonitorProfile) {
+        self.profiles.push(profile);
+    }
+
+    /// Builder variant of [`Self::add_profile`].
+    pub fn with_profile(mut self, profile: MonitorProfile) -> Self {
+        self.add_profile(profile);
+        self
+    }
+
+    /// Returns the name of the profile that was applied last.
+    pub fn active(&self) -> Option<&str> {
+        self.applied.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the first profile that matches `connected`.
+    pub fn find(&self, connected: &[Monitor]) -> Option<&MonitorProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.matches(connected))
+    }
+
+    /// Returns the commands that apply the profile matching `connected`. Nothing is returned when
+    /// no profile matches, or when the same profile was already applied to the same monitors.
+    pub fn update(&mut self, connected: &[Monitor], workspaces: &[Workspace]) -> Vec<Command> {
+        let Some(profile) = self.find(connected) else {
+            self.applied = None;
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = connected
+            .iter()
+            .map(|monitor| monitor.name.clone())
+            .collect();
+        names.sort();
+        let applied = Some((profile.name.clone(), names));
+        if self.applied == applied {
+            return Vec::new();
+        }
+
+        let commands = profile.commands(connected, workspaces).unwrap_or_default();
+        self.applied = applied;
+        commands
+    }
+
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn log_failures(report: &RecipeReport) {
+        for (_, command, e) in report.failures() {
+            warn!(command = command.get_command(), error:% = e; "Monitor profile command failed");
+        }
+    }
+
+    /// Requests the connected monitors and applies the profile that matches them, returns
+    /// whether a profile was applied.
+    #[cfg(feature = "async")]
+    pub async fn apply(&mut self, conn: &HyprlandConnection) -> Result<bool, io::Error> {
+        let monitors = conn
+            .get_with_argument::<Monitors>(MonitorsDataArgument::All)
+            .await?;
+        let workspaces = conn.get::<Workspaces>().await?;
+
+        let commands = self.update(&monitors, &workspaces);
+        if commands.is_empty() {
+            return Ok(false);
+        }
+
+        let report = conn
+            .send_recipe_with_report(&commands, RecipeMode::Continue)
+            .await;
+        Self::log_failures(&report);
+        Ok(true)
+    }
+
+    /// The blocking counterpart of [`Self::apply`].
+    #[cfg(feature = "sync")]
+    pub fn apply_sync(&mut self, conn: &HyprlandConnection) -> Result<bool, io::Error> {
+        let monitors = conn.get_with_argument_sync::<Monitors>(MonitorsDataArgument::All)?;
+        let workspaces = conn.get_sync::<Workspaces>()?;
+
+        let commands = self.update(&monitors, &workspaces);
+        if commands.is_empty() {
+            return Ok(false);
+        }
+
+        let report = conn.send_recipe_with_report_sync(&commands, RecipeMode::Continue);
+        Self::log_failures(&report);
+        Ok(true)
+    }
+
+    /// Applies the matching profile, then applies it again every time a monitor is connected or
+    /// disconnected, until the event channel closes.
+    #[cfg(feature = "async")]
+    pub async fn run(mut self, conn: &mut HyprlandConnection) -> Result<(), io::Error> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let mut rx = conn.listen_to_events(hotplug_filter()).await?;
+        self.apply(conn).await?;
+
+        loop {
+            match rx.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => {
+                    self.apply(conn).await?;
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+
+    /// The blocking counterpart of [`Self::run`].
+    #[cfg(feature = "sync")]
+    pub fn run_sync(mut self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        use crate::events::single_event_sync::DetachedEventConnection;
+
+        let events = DetachedEventConnection::from_connection(
+            conn.with_instance(conn.instance().to_string()),
+            hotplug_filter(),
+        )?;
+        self.apply_sync(conn)?;
+
+        for event in events.matching() {
+            event?;
+            self.apply_sync(conn)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+fn hotplug_filter() -> EventFilter {
+    let mut filter = EventFilter::new_exclude_all();
+    filter.add_event(&HyprlandEventType::MonitorAddedV2);
+    filter.add_event(&HyprlandEventType::MonitorRemoved);
+    filter
+}
+
+#[cfg(test)]
+mod monitors_tests {
+    use super::*;
+    use crate::data::{HyprlandData, Monitors};
+
+    const MONITORS: &str = r#"[{
+        "id": 0, "name": "DP-1", "description": "Dell U2720Q", "make": "Dell", "model": "U2720Q",
+        "serial": "ABC", "width": 2560, "height": 1440, "refreshRate": 143.99600219726562,
+        "x": 0, "y": 0, "activeWorkspace": {"id": 1, "name": "1"},
+        "specialWorkspace": {"id": 0, "name": ""}, "reserved": [0, 0, 0, 0], "scale": 1.25,
+        "transform": 1, "focused": true, "dpmsStatus": true, "vrr": false, "solitary": "0",
+        "activelyTearing": false, "directScanoutTo": "0", "disabled": false,
+        "currentFormat": "XRGB2101010", "mirrorOf": "none", "availableModes": []
+    }, {
+        "id": 1, "name": "HDMI-A-1", "description": "", "make": "", "model": "",
+        "serial": "", "width": 2560, "height": 1440, "refreshRate": 60.0,
+        "x": 2560, "y": 0, "activeWorkspace": {"id": 1, "name": "1"},
+        "specialWorkspace": {"id": 0, "name": ""}, "reserved": [0, 0, 0, 0], "scale": 1.0,
+        "transform": 0, "focused": false, "dpmsStatus": true, "vrr": false, "solitary": "0",
+        "activelyTearing": false, "directScanoutTo": "0", "disabled": false,
+        "currentFormat": "XRGB8888", "mirrorOf": "0", "availableModes": []
+    }]"#;
+
+    #[test]
+    fn test_monitor_config_format() {
+        assert_eq!(
+            MonitorConfig::new("DP-1").to_string(),
+            "DP-1,preferred,auto,auto"
+        );
+        assert_eq!(
+            MonitorConfig::new("desc:Dell U2720Q")
+                .with_refresh_rate(144.0)
+                .with_resolution(2560, 1440)
+                .with_refresh_rate(144.0)
+                .with_position(MonitorPosition::At { x: -2560, y: 0 })
+                .with_scale(1.5)
+                .with_vrr(VrrMode::Fullscreen)
+                .to_string(),
+            "desc:Dell U2720Q,2560x1440@144,-2560x0,1.5,vrr,2"
+        );
+        assert_eq!(
+            MonitorConfig::new("eDP-1")
+                .with_mode(MonitorMode::HighRefreshRate)
+                .with_position(MonitorPosition::AutoLeft)
+                .with_mirror("DP-1")
+                .to_command()
+                .get_command(),
+            "keyword monitor eDP-1,highrr,auto-left,auto,mirror,DP-1"
+        );
+        assert_eq!(
+            MonitorConfig::new("eDP-1")
+                .with_scale(2.0)
+                .with_disabled(true)
+                .to_string(),
+            "eDP-1,disable"
+        );
+    }
+
+    #[test]
+    fn test_monitor_config_from_monitor() {
+        let monitors = Monitors::from_reply(MONITORS).unwrap();
+
+        let config = MonitorConfig::from(&monitors[0]);
+        assert_eq!(config.transform(), Some(MonitorTransform::Rotate90));
+        assert_eq!(
+            config.to_string(),
+            "DP-1,2560x1440@143.996,0x0,1.25,transform,1,bitdepth,10"
+        );
+        assert_eq!(
+            config.with_transform(MonitorTransform::Normal).to_string(),
+            "DP-1,2560x1440@143.996,0x0,1.25,transform,0,bitdepth,10"
+        );
+
+        let configs = MonitorConfig::from_monitors(&monitors);
+        assert_eq!(configs[0].mirror(), None);
+        assert_eq!(
+            configs[1].to_string(),
+            "HDMI-A-1,2560x1440@60,2560x0,1,transform,0,mirror,DP-1"
+        );
+    }
+
+    fn profiles() -> MonitorProfiles {
+        MonitorProfiles::new()
+            .with_profile(
+                MonitorProfile::new("desk").with_monitor(ProfileMonitor::new(
+                    MonitorMatcher::new().with_make("Dell").with_serial("ABC"),
+                    MonitorConfig::new("dell"),
+                )),
+            )
+            .with_profile(
+                MonitorProfile::new("dual")
+                    .with_monitor(
+                        ProfileMonitor::new(MonitorMatcher::new(), MonitorConfig::new("other"))
+                            .with_workspace(WorkspaceArgument::ID(1))
+                            .with_workspace(WorkspaceArgument::ID(5)),
+                    )
+                    .with_monitor(ProfileMonitor::new(
+                        MonitorMatcher::new().with_description("Dell U2720Q"),
+                        MonitorConfig::new("dell")
+                            .with_position(MonitorPosition::At { x: 0, y: 0 }),
+                    )),
+            )
+    }
+
+    #[test]
+    fn test_monitor_profiles_match() {
+        let monitors = Monitors::from_reply(MONITORS).unwrap();
+        let mut profiles = profiles();
+
+        assert_eq!(profiles.find(&monitors[..1]).unwrap().name(), "desk");
+        assert!(profiles.find(&monitors[1..]).is_none());
+        assert!(profiles.update(&monitors[1..], &[]).is_empty());
+
+        let commands = profiles.update(&monitors, &[]);
+        assert_eq!(profiles.active(), Some("dual"));
+        assert_eq!(
+            commands
+                .iter()
+                .map(|command| command.get_command())
+                .collect::<Vec<_>>(),
+            [
+                "keyword monitor HDMI-A-1,preferred,auto,auto",
+                "keyword workspace 1,monitor:HDMI-A-1",
+                "keyword workspace 5,monitor:HDMI-A-1",
+                "keyword monitor DP-1,preferred,0x0,auto",
+            ]
+        );
+        assert!(profiles.update(&monitors, &[]).is_empty());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_monitor_profiles_apply() {
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("monitors all", MONITORS);
+        mock.reply(
+            "workspaces",
+            r#"[{"id": 1, "name": "1", "monitor": "DP-1", "windows": 1, "hasfullscreen": false,
+                 "lastwindow": "0xaa", "lastwindowtitle": "fish"}]"#,
+        );
+
+        let conn = mock.connection();
+        let mut profiles = profiles();
+        assert!(profiles.apply_sync(&conn).unwrap());
+        assert!(!profiles.apply_sync(&conn).unwrap());
+        assert_eq!(
+            mock.received()[2..6],
+            [
+                "keyword monitor HDMI-A-1,preferred,auto,auto",
+                "keyword workspace 1,monitor:HDMI-A-1",
+                "dispatch moveworkspacetomonitor 1 HDMI-A-1",
+                "keyword workspace 5,monitor:HDMI-A-1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Showing notifications and dismissing the ones that were shown.
+//!
+//! A [`Notification`] describes a notification, and a [`NotificationManager`] shows it and keeps
+//! track of the notifications it showed, so they can be dismissed later.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//!
+//! use hyprrust::arguments::{ColorArgument, NotifyIconArgument};
+//! use hyprrust::notifications::{Notification, NotificationManager};
+//! use hyprrust::HyprlandConnection;
+//!
+//! let conn = HyprlandConnection::current()?;
+//! let mut notifications = NotificationManager::new();
+//!
+//! for step in 1..=3 {
+//!     notifications.dismiss_all(&conn).await?;
+//!     let progress = Notification::new(format!("Step {} of 3", step))
+//!         .with_icon(NotifyIconArgument::Info)
+//!         .with_color(ColorArgument::from_hex("#33ccff")?)
+//!         .with_font_size(16)
+//!         .with_duration(Duration::from_secs(30));
+//!     notifications.show(&conn, &progress).await?;
+//!     // ...
+//! }
+//! notifications.dismiss_all(&conn).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::arguments::{Argument, ColorArgument, NotifyIconArgument};
+use crate::commands::Command;
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::{
+    commands::{dismiss_all_notifications, dismiss_notifications},
+    errors::CommandError,
+    HyprlandConnection,
+};
+
+/// A notification shown by Hyprland. By default it has no icon, uses the default color and font
+/// size, and is shown for 5 seconds.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    message: String,
+    icon: NotifyIconArgument,
+    duration: Duration,
+    color: Option<ColorArgument>,
+    font_size: Option<u32>,
+}
+
+impl Notification {
+    pub fn new(message: impl Into<String>) -> Self {
+        Notification {
+            message: message.into(),
+            icon: NotifyIconArgument::NoIcon,
+            duration: Duration::from_secs(5),
+            color: None,
+            font_size: None,
+        }
+    }
+
+    pub fn with_icon(mut self, icon: NotifyIconArgument) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the color of the icon and of the bar that shows the time left.
+    pub fn with_color(mut self, color: ColorArgument) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: u32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the `notify` command that shows this notification.
+    pub fn to_command(&self) -> Command {
+        let color = self
+            .color
+            .as_ref()
+            .map_or("0".to_string(), Argument::to_argument_string);
+        let font_size = self
+            .font_size
+            .map_or(String::new(), |size| format!("fontsize:{} ", size));
+
+        Command::new(Cow::Owned(format!(
+            "notify {} {} {} {}{}",
+            self.icon.to_argument_string(),
+            self.duration.as_millis(),
+            color,
+            font_size,
+            self.message
+        )))
+    }
+}
+
+impl From<&Notification> for Command {
+    fn from(value: &Notification) -> Self {
+        value.to_command()
+    }
+}
+
+/// Shows notifications and remembers the ones that are still visible.
+///
+/// Hyprland keeps a single list of notifications and always dismisses the oldest ones first,
+/// including the ones shown by other clients. The manager can't tell those apart, so what it
+/// reports is only exact when it's the only one showing notifications.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationManager {
+    shown: VecDeque<(Notification, Instant)>,
+}
+
+impl NotificationManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the notifications shown by this manager that haven't expired or been dismissed,
+    /// oldest first.
+    pub fn visible(&mut self) -> impl Iterator<Item = &Notification> {
+        self.forget_expired();
+        self.shown.iter().map(|(notification, _)| notification)
+    }
+
+    fn forget_expired(&mut self) {
+        let now = Instant::now();
+        self.shown.retain(|(_, expires)| *expires > now);
+    }
+
+    fn shown(&mut self, notification: &Notification) {
+        self.shown
+            .push_back((notification.clone(), Instant::now() + notification.duration));
+    }
+
+    /// Forgets the `count` oldest notifications that haven't expired, like Hyprland does when
+    /// dismissing them.
+    fn dismissed(&mut self, count: usize) {
+        self.forget_expired();
+        self.shown.drain(..count.min(self.shown.len()));
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn show(
+        &mut self,
+        conn: &HyprlandConnection,
+        notification: &Notification,
+    ) -> Result<(), CommandError> {
+        conn.send_command(&notification.to_command()).await?;
+        self.shown(notification);
+        Ok(())
+    }
+
+    /// Dismisses the `count` oldest notifications.
+    #[cfg(feature = "async")]
+    pub async fn dismiss_oldest(
+        &mut self,
+        conn: &HyprlandConnection,
+        count: usize,
+    ) -> Result<(), CommandError> {
+        conn.send_command(&dismiss_oldest_command(count)).await?;
+        self.dismissed(count);
+        Ok(())
+    }
+
+    /// Dismisses every notification, including the ones that weren't shown by this manager.
+    #[cfg(feature = "async")]
+    pub async fn dismiss_all(&mut self, conn: &HyprlandConnection) -> Result<(), CommandError> {
+        conn.send_command(&dismiss_all_notifications()).await?;
+        self.shown.clear();
+        Ok(())
+    }
+
+    /// The blocking counterpart of [`Self::show`].
+    #[cfg(feature = "sync")]
+    pub fn show_sync(
+        &mut self,
+        conn: &HyprlandConnection,
+        notification: &Notification,
+    ) -> Result<(), CommandError> {
+        conn.send_command_sync(&notification.to_command())?;
+        self.shown(notification);
+        Ok(())
+    }
+
+    /// The blocking counterpart of [`Self::dismiss_oldest`].
+    #[cfg(feature = "sync")]
+    pub fn dismiss_oldest_sync(
+        &mut self,
+        conn: &HyprlandConnection,
+        count: usize,
+    ) -> Result<(), CommandError> {
+        conn.send_command_sync(&dismiss_oldest_command(count))?;
+        self.dismissed(count);
+        Ok(())
+    }
+
+    /// The blocking counterpart of [`Self::dismiss_all`].
+    #[cfg(feature = "sync")]
+    pub fn dismiss_all_sync(&mut self, conn: &HyprlandConnection) -> Result<(), CommandError> {
+        conn.send_command_sync(&dismiss_all_notifications())?;
+        self.shown.clear();
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "async", feature = "sync"))]
+fn dismiss_oldest_command(count: usize) -> Command {
+    dismiss_notifications(count.try_into().unwrap_or(i32::MAX))
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod notifications_tests {
+    use std::time::Duration;
+
+    use super::{Notification, NotificationManager};
+    use crate::arguments::{ColorArgument, NotifyIconArgument};
+    use crate::testing::MockHyprland;
+
+    #[test]
+    fn test_notification_manager() {
+        let mock = MockHyprland::start().unwrap();
+        let conn = mock.connection();
+        let mut notifications = NotificationManager::new();
+
+        let progress = Notification::new("Copying files")
+            .with_icon(NotifyIconArgument::Info)
+            .with_color(ColorArgument::from_hex("33ccff").unwrap())
+            .with_font_size(16)
+            .with_duration(Duration::from_secs(60));
+        notifications.show_sync(&conn, &progress).unwrap();
+        notifications
+            .show_sync(&conn, &Notification::new("Done"))
+            .unwrap();
+        notifications
+            .show_sync(
+                &conn,
+                &Notification::new("Gone").with_duration(Duration::ZERO),
+            )
+            .unwrap();
+
+        let visible: Vec<&str> = notifications.visible().map(|n| n.message()).collect();
+        assert_eq!(visible, ["Copying files", "Done"]);
+
+        notifications.dismiss_oldest_sync(&conn, 1).unwrap();
+        let visible: Vec<&str> = notifications.visible().map(|n| n.message()).collect();
+        assert_eq!(visible, ["Done"]);
+
+        notifications.dismiss_all_sync(&conn).unwrap();
+        assert_eq!(notifications.visible().count(), 0);
+
+        assert_eq!(
+            mock.received(),
+            [
+                "notify 1 60000 rgba(33ccffff) fontsize:16 Copying files",
+                "notify -1 5000 0 Done",
+                "notify -1 0 0 Gone",
+                "dismissnotify 1",
+                "dismissnotify",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dismiss_skips_expired() {
+        let mock = MockHyprland::start().unwrap();
+        let conn = mock.connection();
+        let mut notifications = NotificationManager::new();
+
+        notifications
+            .show_sync(
+                &conn,
+                &Notification::new("Gone").with_duration(Duration::ZERO),
+            )
+            .unwrap();
+        notifications
+            .show_sync(&conn, &Notification::new("Shown"))
+            .unwrap();
+        notifications
+            .show_sync(&conn, &Notification::new("Also shown"))
+            .unwrap();
+
+        notifications.dismiss_oldest_sync(&conn, 1).unwrap();
+        let visible: Vec<&str> = notifications.visible().map(|n| n.message()).collect();
+        assert_eq!(visible, ["Also shown"]);
+
+        mock.reply("dismissnotify 1", "Failed");
+        mock.reply("dismissnotify", "Failed");
+        assert!(notifications.dismiss_oldest_sync(&conn, 1).is_err());
+        assert!(notifications.dismiss_all_sync(&conn).is_err());
+        let visible: Vec<&str> = notifications.visible().map(|n| n.message()).collect();
+        assert_eq!(visible, ["Also shown"]);
+    }
+}
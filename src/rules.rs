@@ -0,0 +1,393 @@
+//! Rules that react to windows being opened, closed, moved or renamed by sending commands.
+//!
+//! A [`Rule`] pairs a [`RuleTrigger`] and a [`WindowMatcher`] with an action that returns the
+//! commands to send for the matching window. Rules are collected in a [`RuleEngine`], which keeps
+//! a [`HyprlandState`] up to date, so matchers can also look at the workspace and monitor of the
+//! window.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use hyprrust::commands::prelude::*;
+//! use hyprrust::rules::{Regex, Rule, RuleEngine, RuleTrigger, WindowMatcher};
+//! use hyprrust::HyprlandConnection;
+//!
+//! let mut conn = HyprlandConnection::current()?;
+//!
+//! let engine = RuleEngine::new().with_rule(Rule::new(
+//!     RuleTrigger::WindowOpened,
+//!     WindowMatcher::new()
+//!         .with_class(Regex::new("^pavucontrol$")?)
+//!         .with_workspace_id(3),
+//!     |window| {
+//!         vec![
+//!             set_floating(window.into()),
+//!             focus_window(window.into()),
+//!             move_window_to_monitor(MonitorArgument::Name("DP-1".to_string()), true),
+//!         ]
+//!     },
+//! ));
+//!
+//! engine.run(&mut conn).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::io;
+
+#[cfg(any(feature = "async", feature = "sync"))]
+use log::warn;
+pub use regex::Regex;
+
+use crate::commands::Command;
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::commands::RecipeMode;
+use crate::data::Window;
+use crate::events::HyprlandEvent;
+use crate::state::HyprlandState;
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::HyprlandConnection;
+
+/// The window event a rule reacts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleTrigger {
+    WindowOpened,
+    WindowClosed,
+    WindowMoved,
+    WindowTitleChanged,
+}
+
+/// Matches windows by their fields. Every criteria that is set has to match, an empty matcher
+/// matches all windows.
+#[derive(Debug, Clone, Default)]
+pub struct WindowMatcher {
+    class: Option<Regex>,
+    title: Option<Regex>,
+    workspace_id: Option<i64>,
+    workspace_name: Option<String>,
+    monitor: Option<String>,
+}
+
+impl WindowMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_class(mut self, class: Regex) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    pub fn with_title(mut self, title: Regex) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn with_workspace_id(mut self, id: i64) -> Self {
+        self.workspace_id = Some(id);
+        self
+    }
+
+    pub fn with_workspace_name(mut self, name: &str) -> Self {
+        self.workspace_name = Some(name.to_string());
+        self
+    }
+
+    /// Matches windows on the monitor named `name`.
+    pub fn with_monitor(mut self, name: &str) -> Self {
+        self.monitor = Some(name.to_string());
+        self
+    }
+
+    /// Returns whether `window` matches. `state` is used to find the monitor of the window.
+    pub fn matches(&self, window: &Window, state: &HyprlandState) -> bool {
+        if self
+            .class
+            .as_ref()
+            .is_some_and(|class| !class.is_match(&window.class))
+        {
+            return false;
+        }
+        if self
+            .title
+            .as_ref()
+            .is_some_and(|title| !title.is_match(&window.title))
+        {
+            return false;
+        }
+        if self
+            .workspace_id
+            .is_some_and(|id| id != window.workspace.id)
+        {
+            return false;
+        }
+        if self
+            .workspace_name
+            .as_ref()
+            .is_some_and(|name| name != &window.workspace.name)
+        {
+            return false;
+        }
+        if let Some(monitor) = self.monitor.as_ref() {
+            let window_monitor = state
+                .workspace(window.workspace.id)
+                .map(|workspace| workspace.monitor.as_str());
+            if window_monitor != Some(monitor.as_str()) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+type RuleAction = Box<dyn Fn(&Window) -> Vec<Command> + Send + Sync>;
+
+/// Sends the commands returned by `action` when a window matching `matcher` triggers `trigger`.
+pub struct Rule {
+    trigger: RuleTrigger,
+    matcher: WindowMatcher,
+    action: RuleAction,
+}
+
+impl Rule {
+    pub fn new<F>(trigger: RuleTrigger, matcher: WindowMatcher, action: F) -> Self
+    where
+        F: Fn(&Window) -> Vec<Command> + Send + Sync + 'static,
+    {
+        Rule {
+            trigger,
+            matcher,
+            action: Box::new(action),
+        }
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rule")
+            .field("trigger", &self.trigger)
+            .field("matcher", &self.matcher)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A set of rules and the state they are evaluated against.
+///
+/// The engine is `Send`, so it can be moved to its own task or thread with [`Self::run`] or
+/// [`Self::run_sync`]. Events are handled one at a time: the commands of every rule an event
+/// triggered are sent in the order the rules were added, as one recipe, before the next event is
+/// handled. Failed commands are logged and don't stop the engine.
+#[derive(Debug, Default)]
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+    state: HyprlandState,
+}
+
+impl RuleEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_rule(&mut self, rule: Rule) {
+        self.rules.push(rule);
+    }
+
+    /// Builder variant of [`Self::add_rule`].
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// Returns the state the rules are evaluated against.
+    pub fn state(&self) -> &HyprlandState {
+        &self.state
+    }
+
+    /// Updates the state using `event` and returns the commands of every rule it triggered.
+    pub fn handle_event(&mut self, event: &HyprlandEvent) -> Vec<Command> {
+        let (trigger, address) = match event {
+            HyprlandEvent::OpenWindow { address, .. } => (RuleTrigger::WindowOpened, address),
+            HyprlandEvent::CloseWindow { address } => (RuleTrigger::WindowClosed, address),
+            HyprlandEvent::MoveWindowV2 { address, .. } => (RuleTrigger::WindowMoved, address),
+            HyprlandEvent::WindowTitleV2 { address, .. } => {
+                (RuleTrigger::WindowTitleChanged, address)
+            }
+            _ => {
+                self.state.apply(event);
+                return Vec::new();
+            }
+        };
+
+        // A closed window is gone from the state after the event is applied
+        let window = if trigger == RuleTrigger::WindowClosed {
+            let window = self.state.window(address).cloned();
+            self.state.apply(event);
+            window
+        } else {
+            self.state.apply(event);
+            self.state.window(address).cloned()
+        };
+
+        let Some(window) = window else {
+            return Vec::new();
+        };
+
+        self.rules
+            .iter()
+            .filter(|rule| rule.trigger == trigger && rule.matcher.matches(&window, &self.state))
+            .flat_map(|rule| (rule.action)(&window))
+            .collect()
+    }
+
+    #[cfg(any(feature = "async", feature = "sync"))]
+    fn log_failures(report: &crate::commands::RecipeReport) {
+        for (_, command, e) in report.failures() {
+            warn!(command = command.get_command(), error:% = e; "Rule command failed");
+        }
+    }
+
+    /// Seeds the state, then listens to events and sends the commands of the triggered rules
+    /// until the event channel closes.
+    #[cfg(feature = "async")]
+    pub async fn run(mut self, conn: &mut HyprlandConnection) -> Result<(), io::Error> {
+        use crate::events::EventFilter;
+        use tokio::sync::broadcast::error::RecvError;
+
+        self.state.resync(conn).await?;
+        let mut rx = conn
+            .listen_to_events(EventFilter::new_include_all())
+            .await?;
+
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let commands = self.handle_event(&event);
+                    if !commands.is_empty() {
+                        let report = conn
+                            .send_recipe_with_report(&commands, RecipeMode::Continue)
+                            .await;
+                        Self::log_failures(&report);
+                    }
+                }
+                Err(RecvError::Lagged(_)) => self.state.invalidate(),
+                Err(RecvError::Closed) => return Ok(()),
+            }
+
+            if self.state.needs_resync() {
+                self.state.resync(conn).await?;
+            }
+        }
+    }
+
+    /// The blocking counterpart of [`Self::run`].
+    #[cfg(feature = "sync")]
+    pub fn run_sync(mut self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        use crate::events::single_event_sync::DetachedEventConnection;
+        use crate::events::EventFilter;
+
+        self.state.resync_sync(conn)?;
+        let events = DetachedEventConnection::from_connection(
+            conn.with_instance(conn.instance().to_string()),
+            EventFilter::new_include_all(),
+        )?;
+
+        for event in events.matching() {
+            let commands = self.handle_event(&event?);
+            if !commands.is_empty() {
+                let report = conn.send_recipe_with_report_sync(&commands, RecipeMode::Continue);
+                Self::log_failures(&report);
+            }
+
+            if self.state.needs_resync() {
+                self.state.resync_sync(conn)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod rules_tests {
+    use super::*;
+    use crate::commands::prelude::*;
+
+    fn engine() -> RuleEngine {
+        let mut engine = RuleEngine::new()
+            .with_rule(Rule::new(
+                RuleTrigger::WindowOpened,
+                WindowMatcher::new()
+                    .with_class(Regex::new("^kitty$").unwrap())
+                    .with_monitor("DP-1"),
+                |window| vec![set_floating(window.into())],
+            ))
+            .with_rule(Rule::new(
+                RuleTrigger::WindowClosed,
+                WindowMatcher::new().with_title(Regex::new("secret").unwrap()),
+                |_| vec![focus_urgent_or_last()],
+            ));
+
+        for event in [
+            HyprlandEvent::MonitorAddedV2 {
+                id: 0,
+                name: "DP-1".to_string(),
+                description: String::new(),
+            },
+            HyprlandEvent::FocusedMonitorV2 {
+                name: "DP-1".to_string(),
+                workspace_id: 1,
+            },
+            HyprlandEvent::CreateWorkspaceV2 {
+                id: 1,
+                name: "1".to_string(),
+            },
+        ] {
+            assert!(engine.handle_event(&event).is_empty());
+        }
+
+        engine
+    }
+
+    fn open(address: &str, class: &str, title: &str) -> HyprlandEvent {
+        HyprlandEvent::OpenWindow {
+            address: address.to_string(),
+            workspace_name: "1".to_string(),
+            class: class.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rules_match_windows() {
+        let mut engine = engine();
+
+        let commands = engine.handle_event(&open("aa", "kitty", "fish"));
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            commands[0].get_command(),
+            "dispatch setfloating address:0xaa"
+        );
+
+        assert!(engine
+            .handle_event(&open("bb", "firefox", "secret stuff"))
+            .is_empty());
+        let commands = engine.handle_event(&HyprlandEvent::CloseWindow {
+            address: "bb".to_string(),
+        });
+        assert_eq!(commands[0].get_command(), "dispatch focusurgentorlast");
+    }
+
+    #[test]
+    fn test_rules_ignore_other_monitors() {
+        let mut engine = engine();
+
+        engine.handle_event(&HyprlandEvent::MoveWorkspaceV2 {
+            id: 1,
+            name: "1".to_string(),
+            mon_name: "HDMI-A-1".to_string(),
+        });
+        assert!(engine.handle_event(&open("aa", "kitty", "fish")).is_empty());
+    }
+}
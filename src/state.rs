@@ -0,0 +1,512 @@
+//! A local mirror of the compositor state that is kept up to date using events.
+//!
+//! [`HyprlandState`] is seeded with the monitors, workspaces and windows reported by Hyprland and
+//! then updated by feeding it every [`HyprlandEvent`] received. Queries are answered from memory,
+//! so they don't need a round-trip to the socket.
+//!
+//! Some events don't carry enough information to update the mirror (ie. `configreloaded`), and
+//! some events can be missed (ie. when the broadcast channel lags). In those cases the state
+//! reports that it [`needs_resync`] and should be reloaded with [`resync`] or [`resync_sync`].
+//!
+//! [`needs_resync`]: HyprlandState::needs_resync
+//! [`resync`]: HyprlandState::resync
+//! [`resync_sync`]: HyprlandState::resync_sync
+
+use std::collections::{BTreeMap, HashMap};
+#[cfg(any(feature = "async", feature = "sync"))]
+use std::io;
+
+use crate::data::{Monitor, Monitors, Window, Windows, Workspace, WorkspaceBrief, Workspaces};
+use crate::events::HyprlandEvent;
+#[cfg(any(feature = "async", feature = "sync"))]
+use crate::HyprlandConnection;
+
+/// Windows addresses are prefixed with `0x` in data replies, but not in events.
+fn window_key(address: &str) -> String {
+    if address.starts_with("0x") {
+        address.to_string()
+    } else {
+        format!("0x{}", address)
+    }
+}
+
+/// An in-memory copy of the monitors, workspaces and windows of a Hyprland instance.
+#[derive(Debug, Clone, Default)]
+pub struct HyprlandState {
+    monitors: Vec<Monitor>,
+    workspaces: BTreeMap<i64, Workspace>,
+    windows: HashMap<String, Window>,
+    active_window: Option<String>,
+    needs_resync: bool,
+}
+
+impl HyprlandState {
+    /// Creates a new state seeded with the data currently reported by Hyprland.
+    #[cfg(feature = "async")]
+    pub async fn new(conn: &HyprlandConnection) -> Result<Self, io::Error> {
+        let mut state = HyprlandState::default();
+        state.resync(conn).await?;
+        Ok(state)
+    }
+
+    /// The blocking counterpart of [`Self::new`].
+    #[cfg(feature = "sync")]
+    pub fn new_sync(conn: &HyprlandConnection) -> Result<Self, io::Error> {
+        let mut state = HyprlandState::default();
+        state.resync_sync(conn)?;
+        Ok(state)
+    }
+
+    /// Throws away everything known and reloads it from Hyprland.
+    #[cfg(feature = "async")]
+    pub async fn resync(&mut self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        let (monitors, workspaces, windows) =
+            conn.get_batch::<(Monitors, Workspaces, Windows)>().await?;
+        self.load(monitors, workspaces, windows);
+        Ok(())
+    }
+
+    /// The blocking counterpart of [`Self::resync`].
+    #[cfg(feature = "sync")]
+    pub fn resync_sync(&mut self, conn: &HyprlandConnection) -> Result<(), io::Error> {
+        let (monitors, workspaces, windows) =
+            conn.get_batch_sync::<(Monitors, Workspaces, Windows)>()?;
+        self.load(monitors, workspaces, windows);
+        Ok(())
+    }
+
+    fn load(&mut self, monitors: Monitors, workspaces: Workspaces, windows: Windows) {
+        self.monitors = monitors.iter().cloned().collect();
+        self.workspaces = workspaces
+            .iter()
+            .map(|workspace| (workspace.id, workspace.clone()))
+            .collect();
+        self.windows = windows
+            .iter()
+            .map(|window| (window_key(&window.address), window.clone()))
+            .collect();
+        self.active_window = windows
+            .iter()
+            .find(|window| window.focus_history_id == 0)
+            .map(|window| window_key(&window.address));
+        self.needs_resync = false;
+    }
+
+    /// Returns whether the state might have drifted from the compositor and should be reloaded.
+    pub fn needs_resync(&self) -> bool {
+        self.needs_resync
+    }
+
+    /// Marks the state as out of date. Call this when events might have been missed, for example
+    /// when the event receiver reports that it lagged behind.
+    pub fn invalidate(&mut self) {
+        self.needs_resync = true;
+    }
+
+    /// Updates the state using `event`. Events that are not relevant are ignored.
+    pub fn apply(&mut self, event: &HyprlandEvent) {
+        match event {
+            HyprlandEvent::WorkspaceV2 { id, name } => {
+                let brief = WorkspaceBrief {
+                    id: *id,
+                    name: name.clone(),
+                };
+                let monitor_name = self.workspaces.get(id).map(|ws| ws.monitor.clone());
+                match monitor_name.and_then(|name| self.monitor_by_name_mut(&name)) {
+                    Some(monitor) => monitor.active_workspace = brief,
+                    None => self.needs_resync = true,
+                }
+            }
+            HyprlandEvent::FocusedMonitorV2 { name, workspace_id } => {
+                let workspace_name = self
+                    .workspaces
+                    .get(workspace_id)
+                    .map(|ws| ws.name.clone())
+                    .unwrap_or_default();
+                for monitor in self.monitors.iter_mut() {
+                    monitor.focused = &monitor.name == name;
+                    if monitor.focused {
+                        monitor.active_workspace = WorkspaceBrief {
+                            id: *workspace_id,
+                            name: workspace_name.clone(),
+                        };
+                    }
+                }
+            }
+            HyprlandEvent::ActiveWindowV2 { address } => {
+                self.active_window = if address.is_empty() || address == "," {
+                    None
+                } else {
+                    Some(window_key(address))
+                };
+            }
+            HyprlandEvent::Fullscreen { is_fullscreen } => {
+                if let Some(workspace_id) = self.active_window().map(|win| win.workspace.id) {
+                    if let Some(workspace) = self.workspaces.get_mut(&workspace_id) {
+                        workspace.has_fullscreen = *is_fullscreen;
+                    }
+                }
+            }
+            HyprlandEvent::MonitorAddedV2 {
+                id,
+                name,
+                description,
+            } => {
+                self.monitors.retain(|monitor| &monitor.name != name);
+                self.monitors.push(Monitor {
+                    id: *id,
+                    name: name.clone(),
+                    description: description.clone(),
+                    ..Default::default()
+                });
+            }
+            HyprlandEvent::MonitorRemoved { name } => {
+                self.monitors.retain(|monitor| &monitor.name != name);
+            }
+            HyprlandEvent::CreateWorkspaceV2 { id, name } => {
+                let monitor = self
+                    .focused_monitor()
+                    .map(|monitor| monitor.name.clone())
+                    .unwrap_or_default();
+                self.workspaces.insert(
+                    *id,
+                    Workspace {
+                        id: *id,
+                        name: name.clone(),
+                        monitor,
+                        ..Default::default()
+                    },
+                );
+            }
+            HyprlandEvent::DestroyWorkspaceV2 { id, .. } => {
+                self.workspaces.remove(id);
+            }
+            HyprlandEvent::MoveWorkspaceV2 { id, mon_name, .. } => {
+                match self.workspaces.get_mut(id) {
+                    Some(workspace) => workspace.monitor = mon_name.clone(),
+                    None => self.needs_resync = true,
+                }
+            }
+            HyprlandEvent::RenameWorkspace { id, name } => {
+                if let Some(workspace) = self.workspaces.get_mut(id) {
+                    workspace.name = name.clone();
+                }
+                for window in self.windows.values_mut() {
+                    if window.workspace.id == *id {
+                        window.workspace.name = name.clone();
+                    }
+                }
+            }
+            HyprlandEvent::ActiveSpecialV2 { id, name, mon_name } => {
+                if let Some(monitor) = self.monitor_by_name_mut(mon_name) {
+                    monitor.special_workspace = WorkspaceBrief {
+                        id: *id,
+                        name: name.clone(),
+                    };
+                }
+            }
+            HyprlandEvent::OpenWindow {
+                address,
+                workspace_name,
+                class,
+                title,
+            } => {
+                let workspace = self
+                    .workspaces
+                    .values_mut()
+                    .find(|ws| &ws.name == workspace_name);
+                let Some(workspace) = workspace else {
+                    self.needs_resync = true;
+                    return;
+                };
+                workspace.windows += 1;
+                let brief = WorkspaceBrief {
+                    id: workspace.id,
+                    name: workspace.name.clone(),
+                };
+                let monitor_name = workspace.monitor.clone();
+                let monitor = self
+                    .monitors
+                    .iter()
+                    .find(|monitor| monitor.name == monitor_name)
+                    .map_or(-1, |monitor| monitor.id);
+
+                let address = window_key(address);
+                self.windows.insert(
+                    address.clone(),
+                    Window {
+                        address,
+                        mapped: true,
+                        workspace: brief,
+                        monitor,
+                        class: class.clone(),
+                        title: title.clone(),
+                        initial_class: class.clone(),
+                        initial_title: title.clone(),
+                        ..Default::default()
+                    },
+                );
+            }
+            HyprlandEvent::CloseWindow { address } => {
+                let address = window_key(address);
+                match self.windows.remove(&address) {
+                    Some(window) => {
+                        if let Some(workspace) = self.workspaces.get_mut(&window.workspace.id) {
+                            workspace.windows -= 1;
+                        }
+                    }
+                    None => self.needs_resync = true,
+                }
+                if self.active_window.as_ref() == Some(&address) {
+                    self.active_window = None;
+                }
+            }
+            HyprlandEvent::MoveWindowV2 {
+                address,
+                workspace_id,
+                workspace_name,
+            } => {
+                let Some(window) = self.windows.get_mut(&window_key(address)) else {
+                    self.needs_resync = true;
+                    return;
+                };
+                let old_id = window.workspace.id;
+                window.workspace = WorkspaceBrief {
+                    id: *workspace_id,
+                    name: workspace_name.clone(),
+                };
+
+                if let Some(workspace) = self.workspaces.get_mut(&old_id) {
+                    workspace.windows -= 1;
+                }
+                let Some(workspace) = self.workspaces.get_mut(workspace_id) else {
+                    self.needs_resync = true;
+                    return;
+                };
+                workspace.windows += 1;
+                if let Some(monitor) = self
+                    .monitors
+                    .iter()
+                    .find(|monitor| monitor.name == workspace.monitor)
+                {
+                    window.monitor = monitor.id;
+                }
+            }
+            HyprlandEvent::WindowTitleV2 { address, title } => {
+                if let Some(window) = self.windows.get_mut(&window_key(address)) {
+                    window.title = title.clone();
+                }
+            }
+            HyprlandEvent::ChangeFloatingMode {
+                window_address,
+                floating,
+            } => {
+                if let Some(window) = self.windows.get_mut(&window_key(window_address)) {
+                    window.floating = *floating;
+                }
+            }
+            HyprlandEvent::Pin { address, pin_state } => {
+                if let Some(window) = self.windows.get_mut(&window_key(address)) {
+                    window.pinned = *pin_state;
+                }
+            }
+            HyprlandEvent::ConfigReloaded
+            | HyprlandEvent::Disconnected
+            | HyprlandEvent::Reconnected { .. } => {
+                self.needs_resync = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn monitor_by_name_mut(&mut self, name: &str) -> Option<&mut Monitor> {
+        self.monitors
+            .iter_mut()
+            .find(|monitor| monitor.name == name)
+    }
+
+    /// Returns all known monitors.
+    pub fn monitors(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+
+    /// Returns all known workspaces, ordered by id.
+    pub fn workspaces(&self) -> impl Iterator<Item = &Workspace> {
+        self.workspaces.values()
+    }
+
+    /// Returns all known windows.
+    pub fn windows(&self) -> impl Iterator<Item = &Window> {
+        self.windows.values()
+    }
+
+    /// Returns the monitor named `name`.
+    pub fn monitor(&self, name: &str) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.name == name)
+    }
+
+    /// Returns the workspace with the id `id`.
+    pub fn workspace(&self, id: i64) -> Option<&Workspace> {
+        self.workspaces.get(&id)
+    }
+
+    /// Returns the window with the address `address`. The address can be given with or without
+    /// the `0x` prefix.
+    pub fn window(&self, address: &str) -> Option<&Window> {
+        self.windows.get(&window_key(address))
+    }
+
+    /// Returns the windows that are on the workspace with the id `id`.
+    pub fn windows_on_workspace(&self, id: i64) -> impl Iterator<Item = &Window> {
+        self.windows
+            .values()
+            .filter(move |window| window.workspace.id == id)
+    }
+
+    /// Returns the workspaces that are on the monitor named `name`.
+    pub fn workspaces_on_monitor<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Workspace> {
+        self.workspaces
+            .values()
+            .filter(move |workspace| workspace.monitor == name)
+    }
+
+    /// Returns the focused monitor.
+    pub fn focused_monitor(&self) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.focused)
+    }
+
+    /// Returns the active workspace of the focused monitor.
+    pub fn active_workspace(&self) -> Option<&Workspace> {
+        self.focused_monitor()
+            .and_then(|monitor| self.workspaces.get(&monitor.active_workspace.id))
+    }
+
+    /// Returns the focused window.
+    pub fn active_window(&self) -> Option<&Window> {
+        self.active_window
+            .as_ref()
+            .and_then(|address| self.windows.get(address))
+    }
+}
+
+#[cfg(test)]
+mod state_tests {
+    use super::HyprlandState;
+    use crate::events::HyprlandEvent;
+
+    #[cfg(feature = "sync")]
+    const MONITORS: &str = r#"[{
+        "id": 0, "name": "DP-1", "description": "Dell U2720Q", "make": "Dell", "model": "U2720Q",
+        "serial": "ABC", "width": 3840, "height": 2160, "refreshRate": 60.0, "x": 0, "y": 0,
+        "activeWorkspace": {"id": 1, "name": "1"}, "specialWorkspace": {"id": 0, "name": ""},
+        "reserved": [0, 0, 0, 0], "scale": 1.5, "transform": 0, "focused": true,
+        "dpmsStatus": true, "vrr": false, "solitary": "0", "activelyTearing": false,
+        "directScanoutTo": "0", "disabled": false, "currentFormat": "XRGB8888", "mirrorOf": "none",
+        "availableModes": ["3840x2160@60.00Hz"]
+    }]"#;
+
+    #[cfg(feature = "sync")]
+    const WORKSPACES: &str = r#"[
+        {"id": 1, "name": "1", "monitor": "DP-1", "windows": 1, "hasfullscreen": false,
+         "lastwindow": "0xaa", "lastwindowtitle": "fish"},
+        {"id": 3, "name": "3", "monitor": "DP-1", "windows": 0, "hasfullscreen": false,
+         "lastwindow": "0x0", "lastwindowtitle": ""}
+    ]"#;
+
+    #[cfg(feature = "sync")]
+    const WINDOWS: &str = r#"[{
+        "address": "0xaa", "mapped": true, "hidden": false, "at": [0, 0], "size": [100, 100],
+        "workspace": {"id": 1, "name": "1"}, "floating": false, "pseudo": false, "monitor": 0,
+        "class": "kitty", "title": "fish", "initialClass": "kitty", "initialTitle": "fish",
+        "pid": 42, "xwayland": false, "pinned": false, "fullscreen": 0, "fullscreenClient": 0,
+        "grouped": [], "tags": [], "swallowing": "0x0", "focusHistoryID": 0,
+        "inhibitingIdle": false
+    }]"#;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_state_follows_events() {
+        use crate::testing::MockHyprland;
+
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("monitors", MONITORS);
+        mock.reply("workspaces", WORKSPACES);
+        mock.reply("clients", WINDOWS);
+
+        let mut state = HyprlandState::new_sync(&mock.connection()).unwrap();
+        assert_eq!(state.focused_monitor().unwrap().name, "DP-1");
+        assert_eq!(state.active_window().unwrap().class, "kitty");
+        assert_eq!(state.windows_on_workspace(1).count(), 1);
+
+        state.apply(&HyprlandEvent::OpenWindow {
+            address: "bb".to_string(),
+            workspace_name: "1".to_string(),
+            class: "firefox".to_string(),
+            title: "Mozilla Firefox".to_string(),
+        });
+        assert_eq!(state.windows_on_workspace(1).count(), 2);
+        assert_eq!(state.workspace(1).unwrap().windows, 2);
+
+        state.apply(&HyprlandEvent::MoveWindowV2 {
+            address: "bb".to_string(),
+            workspace_id: 3,
+            workspace_name: "3".to_string(),
+        });
+        assert_eq!(state.windows_on_workspace(3).count(), 1);
+        assert_eq!(state.window("0xbb").unwrap().workspace.id, 3);
+        assert_eq!(state.workspace(1).unwrap().windows, 1);
+
+        state.apply(&HyprlandEvent::CloseWindow {
+            address: "aa".to_string(),
+        });
+        assert!(state.active_window().is_none());
+        assert_eq!(state.windows_on_workspace(1).count(), 0);
+        assert!(!state.needs_resync());
+
+        state.apply(&HyprlandEvent::ConfigReloaded);
+        assert!(state.needs_resync());
+        state.resync_sync(&mock.connection()).unwrap();
+        assert!(!state.needs_resync());
+        assert_eq!(state.windows().count(), 1);
+    }
+
+    #[test]
+    fn test_state_workspaces_and_monitors() {
+        let mut state = HyprlandState::default();
+
+        state.apply(&HyprlandEvent::MonitorAddedV2 {
+            id: 1,
+            name: "HDMI-A-1".to_string(),
+            description: "LG".to_string(),
+        });
+        state.apply(&HyprlandEvent::FocusedMonitorV2 {
+            name: "HDMI-A-1".to_string(),
+            workspace_id: 5,
+        });
+        state.apply(&HyprlandEvent::CreateWorkspaceV2 {
+            id: 5,
+            name: "5".to_string(),
+        });
+        assert_eq!(state.workspace(5).unwrap().monitor, "HDMI-A-1");
+        assert_eq!(state.active_workspace().unwrap().id, 5);
+
+        state.apply(&HyprlandEvent::RenameWorkspace {
+            id: 5,
+            name: "web".to_string(),
+        });
+        assert_eq!(state.workspace(5).unwrap().name, "web");
+
+        state.apply(&HyprlandEvent::DestroyWorkspaceV2 {
+            id: 5,
+            name: "web".to_string(),
+        });
+        state.apply(&HyprlandEvent::MonitorRemoved {
+            name: "HDMI-A-1".to_string(),
+        });
+        assert!(state.workspace(5).is_none());
+        assert!(state.focused_monitor().is_none());
+    }
+}
@@ -0,0 +1,344 @@
+//! An in-process fake Hyprland, useful for testing code built on this crate without a running
+//! compositor.
+//!
+//! [`MockHyprland`] binds both sockets under a temporary runtime directory, answers requests on
+//! the control socket with scripted replies and lets you push lines onto the event socket.
+//! This module needs the `testing` feature.
+//!
+//! ```
+//! # #[cfg(feature = "sync")] {
+//! use hyprrust::commands::prelude::*;
+//! use hyprrust::data::CursorPosition;
+//! use hyprrust::testing::MockHyprland;
+//!
+//! let mock = MockHyprland::start().unwrap();
+//! mock.reply("cursorpos", r#"{"x": 10, "y": 20}"#);
+//!
+//! let conn = mock.connection();
+//! assert_eq!(conn.get_sync::<CursorPosition>().unwrap().x, 10);
+//!
+//! conn.send_command_sync(&pin_window(WindowArgument::ActiveWindow))
+//!     .unwrap();
+//! assert_eq!(mock.received().last().unwrap(), "dispatch pin activewindow");
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use crate::HyprlandConnection;
+
+static MOCK_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct MockState {
+    replies: HashMap<String, String>,
+    default_reply: String,
+    received: Vec<String>,
+}
+
+/// A fake Hyprland instance. The sockets are closed and the temporary directory is removed when
+/// this gets dropped.
+#[derive(Debug)]
+pub struct MockHyprland {
+    root: PathBuf,
+    instance: String,
+    state: Arc<Mutex<MockState>>,
+    event_clients: Arc<Mutex<Vec<UnixStream>>>,
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl MockHyprland {
+    /// Creates the runtime directory and starts serving both sockets.
+    pub fn start() -> Result<MockHyprland, io::Error> {
+        let id = MOCK_COUNT.fetch_add(1, Ordering::Relaxed);
+        let instance = format!("mock_{}_{}", process::id(), id);
+        let root = env::temp_dir().join(format!("hyprrust-{}", instance));
+
+        let instance_dir = root.join("hypr").join(&instance);
+        fs::create_dir_all(&instance_dir)?;
+
+        let ctl_listener = UnixListener::bind(instance_dir.join(".socket.sock"))?;
+        let event_listener = UnixListener::bind(instance_dir.join(".socket2.sock"))?;
+
+        let state = Arc::new(Mutex::new(MockState {
+            replies: HashMap::new(),
+            default_reply: "ok".to_string(),
+            received: Vec::new(),
+        }));
+        let event_clients = Arc::new(Mutex::new(Vec::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let ctl_thread = {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || {
+                for stream in ctl_listener.incoming() {
+                    if shutdown.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let _ = serve_request(stream, &state);
+                    }
+                }
+            })
+        };
+
+        let event_thread = {
+            let event_clients = event_clients.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || {
+                for stream in event_listener.incoming() {
+                    if shutdown.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        event_clients.lock().unwrap().push(stream);
+                    }
+                }
+            })
+        };
+
+        Ok(MockHyprland {
+            root,
+            instance,
+            state,
+            event_clients,
+            shutdown,
+            threads: vec![ctl_thread, event_thread],
+        })
+    }
+
+    /// Returns a connection to this instance.
+    pub fn connection(&self) -> HyprlandConnection {
+        HyprlandConnection::with_runtime_dir(self.instance.clone(), self.root.join("hypr"))
+    }
+
+    /// Returns the instance signature of this mock.
+    pub fn instance(&self) -> &str {
+        &self.instance
+    }
+
+    /// Returns the directory that plays the role of `XDG_RUNTIME_DIR`.
+    pub fn runtime_dir(&self) -> &Path {
+        &self.root
+    }
+
+    /// Makes the mock answer `command` with `reply`. The command is matched exactly and without
+    /// flags, so `-j/monitors` is matched by `"monitors"`.
+    pub fn reply(&self, command: &str, reply: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .replies
+            .insert(command.to_string(), reply.to_string());
+    }
+
+    /// Sets the reply used for commands that have no scripted reply. Defaults to `ok`.
+    pub fn set_default_reply(&self, reply: &str) {
+        self.state.lock().unwrap().default_reply = reply.to_string();
+    }
+
+    /// Returns every command received so far, in order, with flags stripped and batches split.
+    pub fn received(&self) -> Vec<String> {
+        self.state.lock().unwrap().received.clone()
+    }
+
+    /// Forgets the commands received so far.
+    pub fn clear_received(&self) {
+        self.state.lock().unwrap().received.clear();
+    }
+
+    /// Returns the number of clients currently connected to the event socket.
+    pub fn event_listener_count(&self) -> usize {
+        self.event_clients.lock().unwrap().len()
+    }
+
+    /// Blocks until at least `count` clients are connected to the event socket. Returns `false`
+    /// if that doesn't happen within `timeout`.
+    pub fn wait_for_event_listeners(&self, count: usize, timeout: Duration) -> bool {
+        let start = Instant::now();
+        while self.event_listener_count() < count {
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        true
+    }
+
+    /// Sends `line` (for example `openwindow>>abc,1,kitty,kitty`) to every client connected to
+    /// the event socket. Clients that went away are dropped.
+    pub fn push_event(&self, line: &str) {
+        let msg = format!("{}\n", line);
+        self.event_clients
+            .lock()
+            .unwrap()
+            .retain_mut(|client| client.write_all(msg.as_bytes()).is_ok());
+    }
+
+    /// Closes every connection to the event socket, like Hyprland does when it exits.
+    pub fn disconnect_event_listeners(&self) {
+        self.event_clients.lock().unwrap().clear();
+    }
+}
+
+impl Drop for MockHyprland {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+
+        // Wake up the threads blocked in accept
+        let instance_dir = self.root.join("hypr").join(&self.instance);
+        let _ = UnixStream::connect(instance_dir.join(".socket.sock"));
+        let _ = UnixStream::connect(instance_dir.join(".socket2.sock"));
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+
+        self.event_clients.lock().unwrap().clear();
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn split_flags(msg: &str) -> &str {
+    match msg.split_once('/') {
+        Some((flags, command)) if flags.chars().all(|c| "-jrac".contains(c)) => command,
+        _ => msg,
+    }
+}
+
+fn serve_request(mut stream: UnixStream, state: &Mutex<MockState>) -> Result<(), io::Error> {
+    let mut buf = vec![0; 65536];
+    let len = stream.read(&mut buf)?;
+    let msg = String::from_utf8_lossy(&buf[..len]);
+    let msg = split_flags(&msg);
+
+    let reply = {
+        let mut state = state.lock().unwrap();
+        let mut reply_for = |command: &str| {
+            state.received.push(command.to_string());
+            state
+                .replies
+                .get(command)
+                .unwrap_or(&state.default_reply)
+                .clone()
+        };
+
+        if let Some(batch) = msg.strip_prefix("[[BATCH]]") {
+            batch
+                .split(';')
+                .map(|command| split_flags(command.trim()))
+                .filter(|command| !command.is_empty())
+                .map(reply_for)
+                .collect::<Vec<_>>()
+                .join("\n\n\n")
+        } else {
+            reply_for(msg)
+        }
+    };
+
+    stream.write_all(reply.as_bytes())
+}
+
+#[cfg(test)]
+mod testing_tests {
+    use super::MockHyprland;
+    #[cfg(feature = "sync")]
+    use crate::{
+        commands::prelude::*,
+        data::{CursorPosition, GetOption, Version},
+    };
+
+    #[cfg(feature = "sync")]
+    const VERSION: &str = r#"{
+        "branch": "main", "commit": "abc", "version": "0.50.0", "dirty": false,
+        "commit_message": "msg", "commit_date": "today", "tag": "v0.50.0", "commits": "1",
+        "buildAquamarine": "0.9", "buildHyprlang": "0.6", "buildHyprutils": "0.8",
+        "buildHyprcursor": "0.1", "buildHyprgraphics": "0.1", "flags": []
+    }"#;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_mock_data() {
+        let mock = MockHyprland::start().unwrap();
+        mock.reply("version", VERSION);
+        mock.reply("cursorpos", r#"{"x": 3, "y": 4}"#);
+        mock.reply(
+            "getoption misc:vfr",
+            r#"{"option": "misc:vfr", "int": 1, "set": true}"#,
+        );
+
+        let conn = mock.connection();
+        assert_eq!(conn.get_sync::<Version>().unwrap().version, "0.50.0");
+        let pos = conn.get_sync::<CursorPosition>().unwrap();
+        assert_eq!((pos.x, pos.y), (3, 4));
+        assert!(conn
+            .get_with_argument_sync::<GetOption>("misc:vfr".to_owned())
+            .is_ok());
+        assert_eq!(
+            mock.received(),
+            ["version", "cursorpos", "getoption misc:vfr"]
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_mock_commands() {
+        let mock = MockHyprland::start().unwrap();
+        let conn = mock.connection();
+
+        conn.send_recipe_sync(&[
+            set_floating(WindowArgument::ActiveWindow),
+            pin_window(WindowArgument::ActiveWindow),
+        ])
+        .unwrap();
+        assert_eq!(
+            mock.received(),
+            [
+                "dispatch setfloating activewindow",
+                "dispatch pin activewindow"
+            ]
+        );
+
+        mock.clear_received();
+        mock.reply("dispatch pin activewindow", "No such window");
+        assert!(conn
+            .send_command_sync(&pin_window(WindowArgument::ActiveWindow))
+            .is_err());
+        assert_eq!(mock.received(), ["dispatch pin activewindow"]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_mock_events() {
+        use crate::events::{EventFilter, HyprlandEvent};
+        use std::time::Duration;
+
+        let mock = MockHyprland::start().unwrap();
+        let mut conn = mock.connection();
+
+        let mut rx = conn
+            .listen_to_events(EventFilter::new_include_all())
+            .await
+            .unwrap();
+        assert!(mock.wait_for_event_listeners(1, Duration::from_secs(1)));
+
+        mock.push_event("openwindow>>abc,1,kitty,fish");
+        match rx.recv().await.unwrap() {
+            HyprlandEvent::OpenWindow { address, class, .. } => {
+                assert_eq!(address, "abc");
+                assert_eq!(class, "kitty");
+            }
+            ev => panic!("unexpected event {:?}", ev),
+        }
+    }
+}